@@ -0,0 +1,414 @@
+// SPDX-License-Identifier: MIT
+// Generated by contracts/build.rs from the #[external] impl; do not edit by hand.
+pragma solidity ^0.8.23;
+
+interface ITrustOracle {
+    struct TrustScore {
+        uint16 score;
+        uint16 confidence;
+        uint32 timestamp;
+        uint32 validUntil;
+        bytes32 source;
+        bytes32 metadataHash;
+    }
+
+    struct TrustScoreV2 {
+        uint16 score;
+        uint16 confidence;
+        uint32 timestamp;
+        bytes32 source;
+        bytes32 blobVersionedHash;
+    }
+
+    function init(address oracleAddress, uint16 trustThreshold, uint16 maxScore, uint16 defaultScore) external;
+
+    function updateScore(address wallet, uint8 category, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 metadataHash, uint32 deadline, uint32 validUntil, address oracle, uint8[] calldata signature) external payable;
+
+    function updateScoreForCategory(address wallet, bytes32 category, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 metadataHash, uint32 deadline, uint32 validUntil, address oracle, uint8[] calldata signature) external payable;
+
+    function forceUpdateScore(address wallet, uint8 category, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 metadataHash, uint32 deadline, uint32 validUntil, address oracle, uint8[] calldata signature) external payable;
+
+    function updateScoreV2(address wallet, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 blobVersionedHash, uint32 deadline, uint8[] calldata signature) external payable;
+
+    function updateScoreMulti(address wallet, uint8 category, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 metadataHash, uint32 deadline, uint32 validUntil, address oracle, uint8[][] calldata signatures) external payable;
+
+    function batchUpdateScores((address,uint8,uint16,uint16,uint32,bytes32,bytes32,uint32,uint32,uint8[])[] calldata updates, address oracle) external payable;
+
+    function updateScoresBatch(address[] calldata wallets, uint8[] calldata categories, uint16[] calldata scores, uint16[] calldata confidences, uint32[] calldata timestamps, bytes32[] calldata sources, bytes32[] calldata metadataHashes, uint32[] calldata deadlines, uint32[] calldata validUntils, address oracle, uint8[][] calldata signatures) external payable returns (uint32);
+
+    function attestBatch(address[] calldata wallets, uint16[] calldata scores, uint16[] calldata confidences, uint32 timestamp, bytes32 source, bytes32 metadataHash, uint32 deadline, uint8[] calldata signature) external payable;
+
+    function getBatchNonce(address oracle) external view returns (uint256);
+
+    function updateMerkleRoot(bytes32 root, uint32 validUntil, uint8[] calldata signature) external;
+
+    function claimScore(address wallet, uint16 score, uint16 confidence, uint32 timestamp, bytes32 source, bytes32 metadataHash, bytes32[] calldata proof) external payable;
+
+    function getMerkleRoot() external view returns (bytes32, uint32);
+
+    function getMerkleNonce(address oracle) external view returns (uint256);
+
+    function revokeScore(address wallet, uint8[] calldata signature) external;
+
+    function revokeScoreWithReason(address wallet, bytes32 reasonHash) external;
+
+    function isRevoked(address wallet) external view returns (bool);
+
+    function revokeScoresBatch(address[] calldata wallets, uint8[][] calldata signatures) external returns (uint32);
+
+    function computeComposite(address wallet) external returns (uint16);
+
+    function getTrustScoreBySource(address wallet, bytes32 source) external view returns (TrustScore memory);
+
+    function getAggregateScore(address wallet) external view returns (uint16);
+
+    function blendScores(address wallet) external returns (uint16);
+
+    function getWalletSources(address wallet) external view returns (bytes32[] memory);
+
+    function getTrustScore(address wallet) external view returns (TrustScore memory);
+
+    function getValidUntil(address wallet) external view returns (uint32);
+
+    function getTrustScoreV2(address wallet) external view returns (TrustScoreV2 memory);
+
+    function getScoreVersion(address wallet) external view returns (uint8);
+
+    function getTrustScoreLabeled(address wallet) external view returns (TrustScore memory, string memory);
+
+    function requireFreshScoreFor(address consumer, address wallet, uint32 maxAge) external view returns (TrustScore memory);
+
+    function requireTrusted(address wallet) external view;
+
+    function hasScore(address wallet) external view returns (bool);
+
+    function isTrusted(address wallet) external view returns (bool);
+
+    function delegateScore(address to) external;
+
+    function revokeDelegation() external;
+
+    function getDelegator(address wallet) external view returns (address);
+
+    function getTrustScores(address[] calldata wallets) external view returns (TrustScore[] memory);
+
+    function areTrusted(address[] calldata wallets) external view returns (bool[] memory);
+
+    function getTrustScoreBatch(address[] calldata wallets) external view returns (TrustScore[] memory);
+
+    function isTrustedBatch(address[] calldata wallets) external view returns (bool[] memory);
+
+    function scorePermit(address wallet, address requester, uint64 deadline, uint8[] calldata signature) external returns (TrustScore memory);
+
+    function getPermitNonce(address wallet) external view returns (uint256);
+
+    function getTrustScoreByCategory(address wallet, uint8 category) external view returns (TrustScore memory);
+
+    function getTrustScoreForCategory(address wallet, bytes32 category) external view returns (TrustScore memory);
+
+    function isTrustedIn(address wallet, bytes32 category) external view returns (bool);
+
+    function isTrustedForCategory(address wallet, uint8 category) external view returns (bool);
+
+    function getEffectiveTrust(address wallet) external view returns (uint16);
+
+    function getConfidence(address wallet) external view returns (uint16);
+
+    function isTrustedWithConfidence(address wallet, uint16 minConfidence) external view returns (bool);
+
+    function getEffectiveScore(address wallet) external view returns (uint16);
+
+    function getScoreDelta(address wallet) external view returns (int32, uint32);
+
+    function getMetadataUri(address wallet) external view returns (string memory);
+
+    function getMetadataBaseUri() external view returns (string memory);
+
+    function getDecayRate() external view returns (uint16);
+
+    function getDecayFloor() external view returns (uint16);
+
+    function isScoreValid(address wallet) external view returns (bool);
+
+    function getScoreAge(address wallet) external view returns (uint32);
+
+    function isScoreStale(address wallet) external view returns (bool);
+
+    function getWalletStatus(address wallet) external view returns (uint16, uint32, uint32, bool, bool);
+
+    function getMaxScoreAge() external view returns (uint32);
+
+    function getMaxTimestampSkew() external view returns (uint32);
+
+    function getScoreTtl() external view returns (uint32);
+
+    function getScoreHistory(address wallet) external view returns (TrustScore[] memory);
+
+    function getRecentScores(address wallet) external view returns (TrustScore[] memory);
+
+    function getTwaScore(address wallet, uint32 windowSeconds) external view returns (uint16);
+
+    function getScoreTrend(address wallet, uint8 window) external view returns (int16);
+
+    function getScoreAtIndex(address wallet, uint32 index) external view returns (TrustScore memory);
+
+    function getTrustScoreAtTimestamp(address wallet, uint32 targetTs) external view returns (TrustScore memory);
+
+    function getHistoryDepth() external view returns (uint8);
+
+    function getMaxScore() external view returns (uint16);
+
+    function getScorePrecision() external view returns (uint8);
+
+    function setScorePrecision(uint8 precision) external;
+
+    function getTrustThreshold() external view returns (uint16);
+
+    function getNonce(address wallet) external view returns (uint256);
+
+    function getNonceForOracle(address wallet, address oracle) external view returns (uint256);
+
+    function getScorePercentile(address wallet) external view returns (uint8);
+
+    function getScoreDistribution() external view returns (uint32[] memory);
+
+    function getTrustedCount() external view returns (uint256);
+
+    function getScoredWalletCount() external view returns (uint64);
+
+    function getScoredWalletAtIndex(uint64 index) external view returns (address);
+
+    function getTrustScoresInRange(uint16 minScore, uint16 maxScore, uint16 maxReturn) external view returns (address[] memory);
+
+    function getTotalScoredWallets() external view returns (uint32);
+
+    function getLeaderboard() external view returns ((address,uint16)[] memory);
+
+    function getMaxLeaderboardSize() external view returns (uint16);
+
+    function isInitialized() external view returns (bool);
+
+    function decimals() external view returns (uint8);
+
+    function description() external view returns (string memory);
+
+    function latestRoundData(address wallet) external view returns (uint80, int256, uint256, uint256, uint80);
+
+    function getRoundData(address wallet, uint80 roundId) external view returns (uint80, int256, uint256, uint256, uint80);
+
+    function getStorageVersion() external view returns (uint32);
+
+    function getChainId() external view returns (uint256);
+
+    function getDomainSeparator() external view returns (bytes32);
+
+    function domainSeparator() external view returns (bytes32);
+
+    function eip712Domain() external view returns (bytes1, string memory, string memory, uint256, address, bytes32, uint256[] memory);
+
+    function proposeOracle(address newOracle) external;
+
+    function activateOracle() external;
+
+    function cancelOracleProposal() external;
+
+    function getPendingOracle() external view returns (address, uint64);
+
+    function activateOracleWeight(address oracle) external;
+
+    function cancelOracleWeightProposal(address oracle) external;
+
+    function getPendingOracleWeight(address oracle) external view returns (uint16, uint64);
+
+    function stakeToBecomeOracle() external payable;
+
+    function withdrawStake() external;
+
+    function slashOracle(address oracle, uint256 amount) external;
+
+    function setOracleStakingRequirement(uint256 amount) external;
+
+    function getOracleStakingRequirement() external view returns (uint256);
+
+    function getStake(address oracle) external view returns (uint256, uint64);
+
+    function withdraw(address to) external;
+
+    function setUpdateFee(uint256 fee) external;
+
+    function getUpdateFee() external view returns (uint256);
+
+    function getCollectedFees() external view returns (uint256);
+
+    function getPreviousOracle() external view returns (address, uint64);
+
+    function updateTrustThreshold(uint16 newThreshold) external;
+
+    function setDefaultScore(uint16 defaultScore) external;
+
+    function getDefaultScore() external view returns (uint16);
+
+    function pause() external;
+
+    function unpause() external;
+
+    function setSourceTtl(bytes32 source, uint32 maxAge) external;
+
+    function setCategoryWeight(uint8 category, uint16 weightBps) external;
+
+    function getCategoryWeight(uint8 category) external view returns (uint16);
+
+    function setSourceWeight(bytes32 source, uint16 weight) external;
+
+    function getSourceWeight(bytes32 source) external view returns (uint16);
+
+    function setMaxScoreAge(uint32 maxAge) external;
+
+    function setMaxTimestampSkew(uint32 seconds) external;
+
+    function setMetadataUri(bytes32 metadataHash, string calldata uri) external;
+
+    function registerSource(bytes32 sourceId, string calldata label) external;
+
+    function getSourceLabel(bytes32 sourceId) external view returns (string memory);
+
+    function getSourceTtl(bytes32 source) external view returns (uint32);
+
+    function setMetadataBaseUri(string calldata uri) external;
+
+    function setScoreNftContract(address nftContract) external;
+
+    function getScoreNftContract() external view returns (address);
+
+    function setMirrorOracle(address mirror) external;
+
+    function clearMirrorOracle() external;
+
+    function getMirrorOracle() external view returns (address);
+
+    function setMirrorPrimary(address primary) external;
+
+    function getMirrorPrimary() external view returns (address);
+
+    function mirrorScore(address wallet, uint8 category, uint16 score, uint16 confidence, uint32 timestamp, uint32 validUntil, bytes32 source, bytes32 metadataHash) external;
+
+    function mirrorRevocation(address wallet, bytes32 reasonHash) external;
+
+    function grantRole(bytes32 role, address account) external;
+
+    function revokeRole(bytes32 role, address account) external;
+
+    function hasRole(bytes32 role, address account) external view returns (bool);
+
+    function setGuardian(address newGuardian) external;
+
+    function getGuardian() external view returns (address);
+
+    function isPaused() external view returns (bool);
+
+    function freezeWallet(address wallet) external;
+
+    function unfreezeWallet(address wallet) external;
+
+    function addRelayer(address relayer) external;
+
+    function removeRelayer(address relayer) external;
+
+    function isRelayer(address relayer) external view returns (bool);
+
+    function setRequireAllowlistedRelayer(bool required) external;
+
+    function isRelayerAllowlistRequired() external view returns (bool);
+
+    function addChallenger(address challenger) external;
+
+    function removeChallenger(address challenger) external;
+
+    function challengeScore(address wallet, bytes32 evidenceHash) external;
+
+    function resolveChallenge(address wallet) external;
+
+    function fileAppeal(bytes32 appealHash) external;
+
+    function resolveAppeal(address wallet, bool accepted, uint16 newScore, uint16 confidence, bytes32 metadataHash) external;
+
+    function getAppeal(address wallet) external view returns (bytes32, uint64);
+
+    function setAppealWindow(uint32 windowSeconds) external;
+
+    function getAppealWindow() external view returns (uint32);
+
+    function isChallenged(address wallet) external view returns (bool);
+
+    function isChallenger(address challenger) external view returns (bool);
+
+    function setBlacklisted(address wallet, bool blacklisted) external;
+
+    function isBlacklisted(address wallet) external view returns (bool);
+
+    function isFrozen(address wallet) external view returns (bool);
+
+    function proposeOwnership(address newOwner) external;
+
+    function transferOwnership(address newOwner) external;
+
+    function acceptOwnership() external;
+
+    function getPendingOwner() external view returns (address);
+
+    function proposeRenounceOwnership() external;
+
+    function executeRenounceOwnership() external;
+
+    function renounceOwnership() external;
+
+    function cancelRenounceOwnership() external;
+
+    function getRenounceAt() external view returns (uint64);
+
+    function setDecayRate(uint16 rateBps) external;
+
+    function setDecayParameters(uint16 rateBps, uint16 floor) external;
+
+    function setMinScoreDelta(uint16 delta) external;
+
+    function setMinUpdateInterval(uint32 interval) external;
+
+    function setMaxScoreDelta(uint16 delta) external;
+
+    function getMaxScoreDelta() external view returns (uint16);
+
+    function getMinScoreDelta() external view returns (uint16);
+
+    function getMinUpdateInterval() external view returns (uint32);
+
+    function setMaxLeaderboardSize(uint16 size) external;
+
+    function setScoreTtl(uint32 ttlSeconds) external;
+
+    function setHistoryDepth(uint8 depth) external;
+
+    function addOracle(address oracle) external;
+
+    function removeOracle(address oracle) external;
+
+    function isOracle(address oracle) external view returns (bool);
+
+    function registerOracle(address oracle, uint16 weight) external;
+
+    function deregisterOracle(address oracle) external;
+
+    function setQuorumThreshold(uint16 quorumThreshold) external;
+
+    function setOracleQuorum(uint16 quorum) external;
+
+    function getOracleQuorum() external view returns (uint16);
+
+    function getOracleWeight(address oracle) external view returns (uint16);
+
+    function getOracles() external view returns (address[] memory);
+
+    function getTotalWeight() external view returns (uint256);
+
+    function getQuorumThreshold() external view returns (uint16);
+}
@@ -0,0 +1,260 @@
+//! Generate the Solidity `ITrustOracle` interface from the `#[external]` impl
+//!
+//! With the `stylus_interface` feature enabled, every `pub fn` of the external impl in
+//! `stylus/src/main.rs` is written to `$OUT_DIR/ITrustOracle.sol` using the Stylus SDK's
+//! ABI rules: names are camelCased, `&self` methods are `view`, `#[payable]` methods are
+//! `payable`, `Result<T, Vec<u8>>` returns `T` and `Vec<u8>` is `uint8[]`. The `sol!`
+//! structs are declared inside the interface; anonymous tuples are written in ABI tuple
+//! notation. A test compares the output to the committed `abi/ITrustOracle.sol`.
+
+use std::{env, fs, path::Path};
+
+const SOURCE: &str = "stylus/src/main.rs";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SOURCE}");
+    if env::var_os("CARGO_FEATURE_STYLUS_INTERFACE").is_none() {
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let source = fs::read_to_string(Path::new(&manifest_dir).join(SOURCE)).expect("contract source is readable");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("ITrustOracle.sol"), interface(&source)).expect("OUT_DIR is writable");
+}
+
+/// Render the interface for a contract source file
+fn interface(source: &str) -> String {
+    let mut out = String::from("// SPDX-License-Identifier: MIT\n");
+    out.push_str("// Generated by contracts/build.rs from the #[external] impl; do not edit by hand.\n");
+    out.push_str("pragma solidity ^0.8.23;\n\ninterface ITrustOracle {\n");
+
+    for (name, fields) in sol_structs(source) {
+        out.push_str(&format!("    struct {name} {{\n"));
+        for field in fields {
+            out.push_str(&format!("        {field};\n"));
+        }
+        out.push_str("    }\n\n");
+    }
+
+    let functions = external_functions(source);
+    for (i, function) in functions.iter().enumerate() {
+        out.push_str(&format!("    {function}\n"));
+        if i + 1 < functions.len() {
+            out.push('\n');
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// `(name, ["type field", ..])` for each struct of the first `sol!` block
+fn sol_structs(source: &str) -> Vec<(String, Vec<String>)> {
+    let block = source
+        .split("\nsol! {\n")
+        .nth(1)
+        .and_then(|rest| rest.split("\n}\n").next())
+        .expect("contract declares its types in a sol! block");
+
+    let mut structs = Vec::new();
+    let mut current: Option<(String, Vec<String>)> = None;
+    for line in block.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if let Some(name) = line.strip_prefix("struct ").and_then(|rest| rest.strip_suffix(" {")) {
+            current = Some((name.to_string(), Vec::new()));
+        } else if line == "}" {
+            structs.extend(current.take());
+        } else if let Some((_, fields)) = current.as_mut() {
+            if let Some(field) = line.strip_suffix(';') {
+                fields.push(field.to_string());
+            }
+        }
+    }
+    structs
+}
+
+/// Solidity declarations of the `pub fn`s in the `#[external]` impl, in source order
+fn external_functions(source: &str) -> Vec<String> {
+    let body = source
+        .split("#[external]\nimpl TrustOracle {\n")
+        .nth(1)
+        .and_then(|rest| rest.split("\n}\n").next())
+        .expect("contract has an #[external] impl");
+
+    let mut functions = Vec::new();
+    let mut payable = false;
+    let mut selector = None;
+    let mut lines = body.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line == "#[payable]" {
+            payable = true;
+        } else if let Some(name) = line.strip_prefix("#[selector(name = \"").and_then(|rest| rest.strip_suffix("\")]")) {
+            selector = Some(name.to_string());
+        } else if line.starts_with("pub fn ") {
+            // The signature ends at the body's opening brace
+            let mut signature = line.to_string();
+            while !signature.ends_with('{') {
+                signature.push(' ');
+                signature.push_str(lines.next().expect("signature is followed by a body").trim());
+            }
+            functions.push(declaration(&signature, payable, selector.take()));
+            payable = false;
+        } else if !line.starts_with("#[") && !line.starts_with("///") {
+            payable = false;
+            selector = None;
+        }
+    }
+    functions
+}
+
+/// Solidity declaration for one `pub fn ... {` signature
+fn declaration(signature: &str, payable: bool, selector: Option<String>) -> String {
+    let rest = signature.strip_prefix("pub fn ").unwrap_or(signature);
+    let open = rest.find('(').expect("function has a parameter list");
+    let close = matching_paren(rest, open);
+    let name = selector.unwrap_or_else(|| camel_case(&rest[..open]));
+
+    let mut params = split_top_level(&rest[open + 1..close]);
+    let receiver = params.remove(0);
+    let params: Vec<String> = params
+        .iter()
+        .map(|param| {
+            let (name, ty) = param.split_once(':').expect("parameter has a type");
+            with_location(sol_type(ty.trim()), "calldata") + " " + &camel_case(name.trim())
+        })
+        .collect();
+
+    let mutability = if !receiver.contains("mut") {
+        " view"
+    } else if payable {
+        " payable"
+    } else {
+        ""
+    };
+
+    let output = rest[close + 1..].trim().trim_end_matches('{').trim();
+    let mut returns = output.strip_prefix("->").map(str::trim).unwrap_or("()");
+    if let Some(ok) = returns.strip_prefix("Result<").and_then(|inner| inner.strip_suffix('>')) {
+        returns = ok.rsplit_once(',').expect("Result has an error type").0.trim();
+    }
+    let returns: Vec<String> = match returns.strip_prefix('(').and_then(|inner| inner.strip_suffix(')')) {
+        Some(items) => split_top_level(items),
+        None => vec![returns.to_string()],
+    }
+    .iter()
+    .map(|ty| with_location(sol_type(ty), "memory"))
+    .collect();
+    let returns = if returns.is_empty() {
+        String::new()
+    } else {
+        format!(" returns ({})", returns.join(", "))
+    };
+
+    format!("function {name}({}) external{mutability}{returns};", params.join(", "))
+}
+
+/// Solidity type of a Rust ABI type
+fn sol_type(ty: &str) -> String {
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|rest| rest.strip_suffix('>')) {
+        return sol_type(inner) + "[]";
+    }
+    if let Some(items) = ty.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')) {
+        let items: Vec<String> = split_top_level(items).iter().map(|item| sol_type(item)).collect();
+        return format!("({})", items.join(","));
+    }
+    if let Some(size) = ty.strip_prefix("FixedBytes<").and_then(|rest| rest.strip_suffix('>')) {
+        return format!("bytes{size}");
+    }
+    match ty {
+        "Address" => "address".to_string(),
+        "bool" => "bool".to_string(),
+        "String" => "string".to_string(),
+        _ => {
+            let bits = |prefixes: [char; 2]| {
+                ty.strip_prefix(prefixes).filter(|bits| bits.parse::<u16>().is_ok())
+            };
+            if let Some(bits) = bits(['u', 'U']) {
+                format!("uint{bits}")
+            } else if let Some(bits) = bits(['i', 'I']) {
+                format!("int{bits}")
+            } else {
+                // A sol! struct declared in the interface
+                ty.to_string()
+            }
+        }
+    }
+}
+
+/// Append a data location to dynamic and struct types
+fn with_location(ty: String, location: &str) -> String {
+    let is_value_type = !ty.ends_with(']')
+        && (ty.starts_with("uint")
+        || ty.starts_with("int")
+        || (ty.starts_with("bytes") && ty.len() > 5)
+        || ty == "address"
+        || ty == "bool");
+    if is_value_type {
+        ty
+    } else {
+        format!("{ty} {location}")
+    }
+}
+
+/// `update_score_v2` -> `updateScoreV2`
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, word) in name.split('_').filter(|word| !word.is_empty()).enumerate() {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                out.push(first);
+            } else {
+                out.extend(first.to_uppercase());
+            }
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// Split on commas that are not nested in `<>` or `()`
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in list.chars() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current.trim().to_string());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Index of the `)` closing the `(` at `open`
+fn matching_paren(text: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip(open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("unbalanced parentheses in `{text}`");
+}
@@ -2,6 +2,7 @@
 pragma solidity ^0.8.20;
 
 import "@openzeppelin/contracts/access/Ownable.sol";
+import "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
 import "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
 import "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
 
@@ -12,7 +13,7 @@ import "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
  * A gas-efficient contract that stores signed TrustScore updates from the backend oracle
  * and allows dApps to query wallet trust scores publicly.
  */
-contract TrustOracle is Ownable {
+contract TrustOracle is Ownable, ReentrancyGuard {
     using ECDSA for bytes32;
     using MessageHashUtils for bytes32;
 
@@ -36,6 +37,12 @@ contract TrustOracle is Ownable {
     /// @dev Nonce mapping to prevent replay attacks
     mapping(address => uint256) public nonces;
 
+    /// @dev Wei a relayer must attach per score update (0 = updates are free)
+    uint256 public updateFee;
+
+    /// @dev Fees paid in since the owner last called withdraw
+    uint256 public collectedFees;
+
     /// @dev Events
     event ScoreUpdated(
         address indexed wallet,
@@ -55,12 +62,29 @@ contract TrustOracle is Ownable {
         uint16 newThreshold
     );
 
+    event Withdrawal(
+        address indexed to,
+        uint256 amount
+    );
+
+    event FeeCollected(
+        address indexed relayer,
+        uint256 amount
+    );
+
+    event UpdateFeeUpdated(
+        uint256 oldFee,
+        uint256 newFee
+    );
+
     /// @dev Custom errors
     error InvalidSignature();
     error UnauthorizedOracle();
     error InvalidScore();
     error StaleTimestamp();
     error InvalidThreshold();
+    error WithdrawalFailed();
+    error InsufficientFee();
 
     /**
      * @dev Constructor
@@ -93,7 +117,14 @@ contract TrustOracle is Ownable {
         bytes32 source,
         bytes32 metadataHash,
         bytes calldata signature
-    ) external {
+    ) external payable {
+        // The whole attached value is kept as fees; overpayment is not refunded
+        if (msg.value < updateFee) revert InsufficientFee();
+        if (msg.value > 0) {
+            collectedFees += msg.value;
+            emit FeeCollected(msg.sender, msg.value);
+        }
+
         // Validate score range
         if (score > 100) revert InvalidScore();
 
@@ -184,6 +215,35 @@ contract TrustOracle is Ownable {
         emit TrustThresholdUpdated(oldThreshold, newThreshold);
     }
 
+    /**
+     * @dev Update the per-update fee (owner only)
+     * @param newFee Wei per update, 0 makes updates free
+     */
+    function setUpdateFee(uint256 newFee) external onlyOwner {
+        uint256 oldFee = updateFee;
+        updateFee = newFee;
+
+        emit UpdateFeeUpdated(oldFee, newFee);
+    }
+
+    /**
+     * @dev Accept plain ETH transfers so they can later be recovered with `withdraw`
+     */
+    receive() external payable {}
+
+    /**
+     * @dev Send the contract's whole ETH balance to `to` (owner only)
+     * @param to Recipient of the balance
+     */
+    function withdraw(address payable to) external onlyOwner nonReentrant {
+        uint256 amount = address(this).balance;
+        collectedFees = 0;
+        (bool success, ) = to.call{value: amount}("");
+        if (!success) revert WithdrawalFailed();
+
+        emit Withdrawal(to, amount);
+    }
+
     /**
      * @dev Create message hash for signature verification
      * @param wallet Wallet address
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/**
+ * @title ITrustOracle
+ * @dev Minimal read interface of the TrustGrid.AI Stylus oracle for integrating contracts
+ */
+interface ITrustOracle {
+    /// @dev Aggregate trust score as stored by the oracle
+    struct TrustScore {
+        uint16 score;           // Score from 0 to the oracle's max score
+        uint16 confidence;      // How much data backed the score, 0-100
+        uint32 timestamp;       // Unix timestamp
+        uint32 validUntil;      // Oracle-signed expiry, 0 for none
+        bytes32 source;         // Source identifier
+        bytes32 metadataHash;   // Hash of explanation metadata
+    }
+
+    /// @dev Raised by `requireTrusted` and `TrustCheck.checkAndRevert`
+    error WalletNotTrusted(address wallet);
+
+    function getTrustScore(address wallet) external view returns (TrustScore memory);
+
+    function isTrusted(address wallet) external view returns (bool);
+
+    function requireTrusted(address wallet) external view;
+}
+
+/**
+ * @title TrustCheck
+ * @dev Gate a call on a wallet's trust status with a single static call
+ */
+library TrustCheck {
+    /**
+     * @dev Revert with `WalletNotTrusted(wallet)` unless the oracle trusts `wallet`
+     * @param oracle The trust oracle to query
+     * @param wallet The wallet to check
+     */
+    function checkAndRevert(ITrustOracle oracle, address wallet) internal view {
+        if (!oracle.isTrusted(wallet)) {
+            revert ITrustOracle.WalletNotTrusted(wallet);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "../interfaces/ITrustOracle.sol";
+
+/**
+ * @title MockTrustConsumer
+ * @dev Test consumer whose guarded action is only open to trusted wallets
+ */
+contract MockTrustConsumer {
+    using TrustCheck for ITrustOracle;
+
+    ITrustOracle public immutable trustOracle;
+
+    uint256 public guardedCalls;
+
+    constructor(address _trustOracle) {
+        trustOracle = ITrustOracle(_trustOracle);
+    }
+
+    /**
+     * @dev Succeeds only when the caller is trusted
+     */
+    function guardedAction() external {
+        trustOracle.checkAndRevert(msg.sender);
+        guardedCalls++;
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "../interfaces/ITrustOracle.sol";
+
+/**
+ * @title MockTrustOracle
+ * @dev Test stand-in for the Stylus oracle, exposing exactly its `ITrustOracle` ABI
+ */
+contract MockTrustOracle is ITrustOracle {
+    uint16 public immutable trustThreshold;
+
+    mapping(address => TrustScore) private trustScores;
+
+    constructor(uint16 _trustThreshold) {
+        trustThreshold = _trustThreshold;
+    }
+
+    /**
+     * @dev Store a score as the oracle would after a verified update
+     */
+    function setScore(address wallet, uint16 score) external {
+        trustScores[wallet] = TrustScore({
+            score: score,
+            confidence: 100,
+            timestamp: uint32(block.timestamp),
+            validUntil: 0,
+            source: bytes32(0),
+            metadataHash: bytes32(0)
+        });
+    }
+
+    function getTrustScore(address wallet) external view returns (TrustScore memory) {
+        return trustScores[wallet];
+    }
+
+    function isTrusted(address wallet) public view returns (bool) {
+        TrustScore memory trustScore = trustScores[wallet];
+        return trustScore.timestamp != 0 && trustScore.score >= trustThreshold;
+    }
+
+    function requireTrusted(address wallet) external view {
+        if (!isTrusted(wallet)) {
+            revert WalletNotTrusted(wallet);
+        }
+    }
+}
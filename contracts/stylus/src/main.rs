@@ -7,29 +7,277 @@
 extern crate alloc;
 
 use stylus_sdk::{
-    alloy_primitives::{Address, U256, FixedBytes},
-    alloy_sol_types::sol,
-    call::Call,
+    alloy_primitives::{aliases::U80, fixed_bytes, Address, FixedBytes, I256, U256},
+    alloy_sol_types::{sol, SolError},
+    block,
+    call::{transfer_eth, Call},
+    contract,
+    crypto::keccak,
     evm, msg,
     prelude::*,
+    types::AddressVM,
 };
-use alloc::{vec::Vec, string::String};
+use alloc::{vec, vec::Vec, string::String};
+
+/// EIP-712 domain name
+const DOMAIN_NAME: &[u8] = b"TrustGrid";
+
+/// EIP-712 domain version written to storage on init
+const DOMAIN_VERSION: &[u8] = b"1";
+
+/// keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+const EIP712_DOMAIN_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f");
+
+/// keccak256("TrustScore(address wallet,uint8 category,uint16 score,uint16 confidence,uint32 timestamp,bytes32 source,bytes32 metadataHash,uint32 deadline,uint32 validUntil,bool force,address oracle,uint256 nonce)")
+const TRUST_SCORE_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("43cf3b4ebee59dd34e875df5e79a4468c0036f2a90396a0510ea0512d9ffa50c");
+
+/// keccak256("TrustScoreV2(address wallet,uint16 score,uint16 confidence,uint32 timestamp,bytes32 source,bytes32 blobVersionedHash,uint32 deadline,address oracle,uint256 nonce)")
+const TRUST_SCORE_V2_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("64dea667de51c6b355590cfc46c513077b139affc45b93dbd7296ab9eba94296");
+
+/// First byte of an EIP-4844 versioned hash for KZG commitments
+const BLOB_COMMITMENT_VERSION_KZG: u8 = 0x01;
+
+/// keccak256("RevokeScore(address wallet,address oracle,uint256 nonce)")
+const REVOKE_SCORE_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("afdf3b0ea41d6881e4eaefa4f56d21b38e8feaca240f08c0f9069846528c4b1d");
+
+/// keccak256("MerkleRoot(bytes32 root,uint32 validUntil,address oracle,uint256 nonce)")
+const MERKLE_ROOT_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("e89d15ec6067fb6fd5f673c485f1530ad835380a0833ed4c5814760fddb5feea");
+
+/// keccak256("ScoreBatch(address[] wallets,uint16[] scores,uint16[] confidences,uint32 timestamp,bytes32 source,bytes32 metadataHash,uint32 deadline,address oracle,uint256 nonce)")
+const SCORE_BATCH_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("2fc6f325c7239319b3d18b584e6302380f54815bc685d95e647920abd78d4fac");
+
+/// keccak256("ScorePermit(address wallet,address requester,uint256 nonce,uint64 deadline)")
+const SCORE_PERMIT_TYPEHASH: FixedBytes<32> =
+    fixed_bytes!("2d8610ce54bc97c3766938c7de91ad634896f3d2517da108b85242c59ebd4018");
+
+/// keccak256("ORACLE_ROLE"); held by exactly the registered oracles
+pub const ORACLE_ROLE: FixedBytes<32> =
+    fixed_bytes!("68e79a7bf1e0bc45d0a330c573bc367f9cf464fd326078812f301165fbda4ef1");
+
+/// keccak256("ADMIN_ROLE"); manages roles, the oracle and the trust threshold
+pub const ADMIN_ROLE: FixedBytes<32> =
+    fixed_bytes!("a49807205ce4d355092ef5a8a18f56e8913cf4a201fbe287825b095693c21775");
+
+/// keccak256("PAUSER_ROLE"); may pause and unpause score mutations
+pub const PAUSER_ROLE: FixedBytes<32> =
+    fixed_bytes!("65d7a28e3265b37a6474929f336521b332c1681b933f6cb9f3376673440d862a");
+
+/// keccak256("TrustGrid.composite"), the source recorded on scores built by `compute_composite`
+const COMPOSITE_SOURCE: FixedBytes<32> =
+    fixed_bytes!("07bdc3037f2ac16d8d4b7263f860a8ee1a2f80885e3e9e831bc4700a0c164446");
+
+/// keccak256("TrustGrid.blend"), the source recorded on scores built by `blend_scores`
+const BLEND_SOURCE: FixedBytes<32> =
+    fixed_bytes!("37ba3d9ff691bd5a61e05910f9f506883b0f17232d79e99ba7391656c6f3c4b1");
+
+/// keccak256("TrustGrid.appeal"), the source recorded on scores set by an accepted appeal
+const APPEAL_SOURCE: FixedBytes<32> =
+    fixed_bytes!("18c962275d7dc379b50cc021a5dc0ec48d98ec9fbb8729773d2e9d1a31f0b2a4");
+
+/// secp256k1 curve order divided by two; larger `s` values are the malleable twin (EIP-2)
+const SECP256K1N_HALF: U256 = U256::from_be_bytes(
+    fixed_bytes!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0").0,
+);
+
+/// Maximum number of wallets accepted by the bulk read views
+///
+/// Off-chain `eth_call`s pay no gas, but an on-chain caller pays for every storage
+/// read (several cold SLOADs per wallet), so contracts should pass far smaller batches.
+const MAX_BATCH_READ: usize = 500;
+
+/// Maximum number of distinct sources tracked per wallet, bounding `get_aggregate_score`
+const MAX_SOURCES_PER_WALLET: usize = 16;
+
+/// Seconds between proposing and executing an ownership renunciation
+const RENOUNCE_TIMELOCK_SECONDS: u64 = 604_800;
+
+/// Maximum accepted score age on a fresh deployment, for sources without their own TTL
+const DEFAULT_MAX_SCORE_AGE: u32 = 3600;
+
+/// Maximum accepted age of a submitted update's timestamp on a fresh deployment
+const DEFAULT_MAX_TIMESTAMP_SKEW: u32 = 3600;
+
+/// Bounds accepted for the timestamp skew and the default staleness window: one minute to one week
+const MIN_TIMESTAMP_SKEW: u32 = 60;
+const MAX_TIMESTAMP_SKEW: u32 = 604_800;
+
+/// Seconds a proposed oracle must wait before it can be activated
+const ORACLE_TIMELOCK_SECONDS: u64 = 86_400;
+
+/// Seconds after an oracle rotation during which the retired key's score updates still verify
+const ORACLE_ROTATION_GRACE_SECONDS: u64 = 3600;
+
+/// Seconds a removed oracle's stake stays locked so it can still be slashed
+const STAKE_COOLDOWN_SECONDS: u64 = 604_800;
+
+/// History entries kept per wallet on a fresh deployment
+const DEFAULT_HISTORY_DEPTH: u8 = 8;
+
+/// Gas forwarded to the score NFT's `mint`, so a hostile contract cannot burn the update's gas
+const SCORE_NFT_MINT_GAS: u64 = 150_000;
+
+/// Gas forwarded to the mirror oracle's `mirrorScore`, for the same reason
+const MIRROR_CALL_GAS: u64 = 200_000;
+
+/// bytes4(keccak256("isValidSignature(bytes32,bytes)")), returned by valid ERC-1271 signers
+const ERC1271_MAGIC_VALUE: FixedBytes<4> = fixed_bytes!("1626ba7e");
+
+/// Lowercase hex alphabet used to render `metadataHash` in URIs
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Storage layout version, bumped whenever a field is added to `TrustOracle`
+const STORAGE_VERSION: u32 = 1;
+
+/// Upper bound of the score scale when `init` is given 0
+const DEFAULT_MAX_SCORE: u16 = 100;
+
+/// Upper bound of the score scale at basis-point precision
+const BASIS_POINT_MAX_SCORE: u16 = 10_000;
+
+/// Upper bound on `max_leaderboard_size`, keeping leaderboard maintenance O(256)
+const MAX_LEADERBOARD_SIZE: u16 = 256;
+
+/// keccak256("TrustGrid.category.aggregate"), the tag the single-score API reads and writes
+const DEFAULT_CATEGORY: FixedBytes<32> =
+    fixed_bytes!("5904e34ac93f3be01a498ea4646749002434baa8e846ff4c5a58f6ffb1d95d96");
+
+/// Trust dimensions a score can be recorded under
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ScoreCategory {
+    Aggregate = 0,
+    DeFi = 1,
+    Nft = 2,
+    Dao = 3,
+    Social = 4,
+    Gaming = 5,
+}
+
+impl ScoreCategory {
+    /// Every category, in encoding order
+    pub const ALL: [ScoreCategory; 6] = [
+        Self::Aggregate,
+        Self::DeFi,
+        Self::Nft,
+        Self::Dao,
+        Self::Social,
+        Self::Gaming,
+    ];
+
+    /// Decode a category from its `u8` ABI representation
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Aggregate),
+            1 => Some(Self::DeFi),
+            2 => Some(Self::Nft),
+            3 => Some(Self::Dao),
+            4 => Some(Self::Social),
+            5 => Some(Self::Gaming),
+            _ => None,
+        }
+    }
+
+    /// The category's bytes32 tag: keccak256("TrustGrid.category.<name>")
+    pub fn tag(self) -> FixedBytes<32> {
+        match self {
+            Self::Aggregate => DEFAULT_CATEGORY,
+            Self::DeFi => fixed_bytes!("0b8cfeb0e5000ccedbc2cd0984cbe653b9465d96586461da78fcbb2c19b181ad"),
+            Self::Nft => fixed_bytes!("61199760dae23b968422a36dfb292e5335a5467a874dcc2cb230bd1996816443"),
+            Self::Dao => fixed_bytes!("2f87cd0abd19ac2e5dcfb6c2565869857acde9da4cf92ee80d34cbc15ca8e2b6"),
+            Self::Social => fixed_bytes!("2a42bf7636061511b7e006fb582351c20eccd6b2905f4e7feb2a0ecf513f3f72"),
+            Self::Gaming => fixed_bytes!("2f1d5dc2ed225912b2f96edb99ee83dd2bc30ca506126cf9f4bc5a25ab41ac20"),
+        }
+    }
+
+    /// Decode a category from its bytes32 tag; the zero tag selects `DEFAULT_CATEGORY`
+    pub fn from_tag(tag: FixedBytes<32>) -> Option<Self> {
+        if tag == FixedBytes::ZERO {
+            return Some(Self::Aggregate);
+        }
+        Self::ALL.into_iter().find(|category| category.tag() == tag)
+    }
+}
+
+/// A score update as signed by the oracle
+struct ScoreUpdate {
+    wallet: Address,
+    category: u8,
+    score: u16,
+    confidence: u16,
+    timestamp: u32,
+    source: FixedBytes<32>,
+    metadata_hash: FixedBytes<32>,
+    deadline: u32,
+    /// Last block timestamp at which the score counts as trusted (0 = no expiry)
+    valid_until: u32,
+    /// Signed consent to bypass the `max_score_delta` circuit breaker
+    force: bool,
+}
 
 // Define the TrustScore structure
 sol! {
     struct TrustScore {
-        uint16 score;        // Score from 0-100
+        uint16 score;        // Score from 0 to `max_score`
+        uint16 confidence;   // How much data backed the score, 0-100
         uint32 timestamp;    // Unix timestamp
+        uint32 validUntil;   // Oracle-signed expiry, 0 for none
         bytes32 source;      // Source identifier
         bytes32 metadataHash; // Hash of explanation metadata
     }
 
+    struct TrustScoreV2 {
+        uint16 score;        // Score from 0 to `max_score`
+        uint16 confidence;   // How much data backed the score, 0-100
+        uint32 timestamp;    // Unix timestamp
+        bytes32 source;      // Source identifier
+        bytes32 blobVersionedHash; // EIP-4844 versioned hash of the blob holding the metadata
+    }
+
+    event ScoreUpdatedV2(
+        address indexed wallet,
+        uint16 score,
+        uint16 confidence,
+        uint32 timestamp,
+        bytes32 indexed source,
+        bytes32 blobVersionedHash
+    );
+
     event ScoreUpdated(
         address indexed wallet,
+        uint8 category,
         uint16 score,
+        uint16 confidence,
         uint32 timestamp,
-        bytes32 source,
-        bytes32 metadataHash
+        bytes32 indexed source,
+        bytes32 metadataHash,
+        uint32 deadline,
+        uint32 validUntil
+    );
+
+    event Staked(address indexed oracle, uint256 amount);
+
+    event StakeSlashed(address indexed oracle, uint256 amount);
+
+    event StakeWithdrawn(address indexed oracle, uint256 amount);
+
+    event Withdrawal(address indexed to, uint256 amount);
+
+    event FeeCollected(address indexed relayer, uint256 amount);
+
+    event UpdateFeeUpdated(uint256 fee);
+
+    event StakingRequirementUpdated(uint256 amount);
+
+    event OracleRotationScheduled(
+        address indexed previousOracle,
+        address indexed newOracle,
+        uint64 expiry
     );
 
     event OracleUpdated(
@@ -37,207 +285,7447 @@ sol! {
         address indexed newOracle
     );
 
+    event OracleProposed(
+        address indexed proposed,
+        uint64 activation_time
+    );
+
+    event OracleProposalCancelled(address indexed proposed);
+
+    event OracleWeightProposed(
+        address indexed oracle,
+        uint16 weight,
+        uint64 activation_time
+    );
+
+    event DefaultScoreUpdated(uint16 default_score);
+
+    event ScorePrecisionChanged(uint8 new_precision);
+
+    event ThresholdUpdated(
+        uint16 oldThreshold,
+        uint16 newThreshold,
+        address indexed by
+    );
+
+    event OracleWeightUpdated(
+        address indexed oracle,
+        uint16 weight
+    );
+
+    event MerkleRootUpdated(
+        bytes32 indexed root,
+        uint32 validUntil,
+        address indexed oracle
+    );
+
+    event BatchAttested(
+        address indexed oracle,
+        uint256 nonce,
+        uint32 count
+    );
+
+    event ScoresBlended(
+        address indexed wallet,
+        uint16 new_score,
+        uint32 timestamp
+    );
+
+    event CompositeComputed(
+        address indexed wallet,
+        uint16 new_score,
+        uint32 timestamp
+    );
+
+    event CategoryWeightUpdated(
+        uint8 indexed category,
+        uint16 weight_bps
+    );
+
+    event ScorePermitUsed(
+        address indexed wallet,
+        address indexed requester,
+        uint256 nonce
+    );
+
+    event ScoreThresholdCrossed(
+        address indexed wallet,
+        bool is_now_trusted,
+        uint16 new_score,
+        uint32 timestamp
+    );
+
+    event ScoreRevoked(
+        address indexed wallet,
+        address indexed oracle,
+        bytes32 reason_hash,
+        uint32 revoked_at
+    );
+
+    event ScoreNftContractUpdated(address indexed nft_contract);
+
+    event ScoreNftMintFailed(address indexed wallet, uint256 token_id);
+
+    event MirrorOracleUpdated(address indexed mirror);
+
+    event MirrorPrimaryUpdated(address indexed primary);
+
+    event MirrorUpdateFailed(address indexed wallet);
+
+    event BatchEntryFailed(
+        uint256 index,
+        address indexed wallet,
+        bytes reason
+    );
+
+    event ScoreExpired(
+        address indexed wallet,
+        uint32 expired_at
+    );
+
+    event OwnershipTransferProposed(
+        address indexed oldOwner,
+        address indexed proposedOwner
+    );
+
+    event OwnershipTransferred(
+        address indexed oldOwner,
+        address indexed newOwner
+    );
+
+    event OwnershipRenounceProposed(uint64 effective_at);
+
+    event OwnershipRenounceCancelled();
+
+    event OwnershipRenounced();
+
+    event SourceTTLUpdated(
+        bytes32 indexed source,
+        uint32 new_ttl
+    );
+
+    event MaxScoreAgeUpdated(
+        uint32 oldMaxAge,
+        uint32 newMaxAge
+    );
+
+    event TimestampSkewUpdated(uint32 new_skew);
+
+    event RoleGranted(
+        bytes32 indexed role,
+        address indexed account,
+        address indexed sender
+    );
+
+    event RoleRevoked(
+        bytes32 indexed role,
+        address indexed account,
+        address indexed sender
+    );
+
+    event GuardianUpdated(
+        address indexed oldGuardian,
+        address indexed newGuardian
+    );
+
+    event MetadataBaseURIUpdated(string uri);
+
+    event MetadataUriSet(bytes32 indexed metadataHash, string uri);
+
+    event Paused(address indexed by);
+
+    event Unpaused(address indexed by);
+
+    event OracleAdded(address indexed oracle);
+
+    event OracleRemoved(address indexed oracle);
+
+    event SourceRegistered(
+        bytes32 indexed source,
+        string label
+    );
+
+    event SourceWeightUpdated(
+        bytes32 indexed source,
+        uint16 weight
+    );
+
+    event DelegationGranted(address indexed from, address indexed to);
+
+    event DelegationRevoked(address indexed from, address indexed to);
+
+    event ChallengerAdded(address indexed challenger);
+
+    event ChallengerRemoved(address indexed challenger);
+
+    event ScoreChallenged(
+        address indexed wallet,
+        address indexed challenger,
+        bytes32 evidence_hash,
+        uint32 timestamp
+    );
+
+    event ChallengeResolved(address indexed wallet, address indexed oracle);
+
+    event AppealFiled(address indexed wallet, bytes32 appeal_hash, uint64 filed_at);
+
+    event AppealResolved(address indexed wallet, bool accepted, uint16 new_score);
+
+    event AppealWindowUpdated(uint32 window_seconds);
+
+    event BlacklistUpdated(address indexed wallet, bool blacklisted);
+
+    event RelayerAdded(address indexed relayer, address indexed by);
+
+    event RelayerRemoved(address indexed relayer, address indexed by);
+
+    event RelayerAllowlistToggled(bool required);
+
+    event WalletFrozen(address indexed wallet, address indexed by);
+
+    event WalletUnfrozen(address indexed wallet, address indexed by);
+
     error InvalidSignature();
     error UnauthorizedOracle();
     error InvalidScore();
     error StaleTimestamp();
+    error AlreadyInitialized();
+    error NotOwner();
+    error NotPendingOwner();
+    error InvalidAddress();
+    error InvalidThreshold();
+    error InvalidCategory();
+    error InvalidQuorum();
+    error InvalidOracleWeight();
+    error OracleAlreadyAuthorized();
+    error OracleNotRegistered();
+    error DuplicateOracleSigner();
+    error QuorumNotMet();
+    error SignatureExpired();
+    error ContractPaused();
+    error BatchLengthMismatch();
+    error HistoryIndexOutOfRange();
+    error BatchTooLarge();
+    error InvalidDecayRate();
+    error ScoreFrozen();
+    error TooManySources();
+    error DelegationExists();
+    error NoDelegation();
+    error NoPendingOracle();
+    error InvalidMaxScoreAge();
+    error InvalidTimestampSkew();
+    error MerkleRootExpired();
+    error NoRenouncePending();
+    error RenounceTimelockActive();
+    error NotChallenger();
+    error MissingRole();
+    error ScoreChangeInsignificant();
+    error UpdateTooFrequent();
+    error ScoreDeltaTooLarge();
+    error UnauthorizedRelayer();
+    error InvalidLeaderboardSize();
+    error NonMonotonicTimestamp();
+    error InvalidTimestamp();
+    error IndexOutOfRange();
+    error NoCategoryScores();
+    error InvalidCategoryWeight();
+    error InvalidSourceWeight();
+    error InvalidBlobHash();
+    error InsufficientStake();
+    error InsufficientFee();
+    error StakeLocked();
+    error WalletNotTrusted(address wallet);
+    error AppealPending();
+    error NoOpenAppeal();
+    error ReentrancyGuardTriggered();
+    error NotMirrorPrimary();
+    error InvalidScorePrecision();
+    error NoSourceScores();
+    error NoStake();
+    error NotPermitRequester();
+    error InvalidMerkleProof();
+    error OracleTimelockActive();
+}
+
+sol_interface! {
+    interface IERC1271 {
+        function isValidSignature(bytes32 hash, bytes memory signature) external view returns (bytes4 magicValue);
+    }
+
+    interface IScoreNFT {
+        function mint(address to, uint256 tokenId, uint16 score, uint32 timestamp) external;
+    }
+
+    interface IMirrorOracle {
+        function mirrorScore(address wallet, uint8 category, uint16 score, uint16 confidence, uint32 timestamp, uint32 validUntil, bytes32 source, bytes32 metadataHash) external;
+
+        function mirrorRevocation(address wallet, bytes32 reasonHash) external;
+    }
 }
 
 // Contract storage
+//
+// Fields occupy slots in declaration order, Solidity-style: consecutive small values
+// share a slot and every mapping or dynamic array takes one slot of its own. Behind a
+// proxy, never reorder or remove fields; add new ones directly above `storage_gap`,
+// shrink the gap by the slots they use and bump `STORAGE_VERSION`.
 sol_storage! {
     #[entrypoint]
     pub struct TrustOracle {
-        /// Mapping from wallet address to their trust score
-        mapping(address => TrustScore) public trust_scores;
-        
+        /// Aggregate trust score of each wallet; other categories live in `trust_scores_by_category`
+        mapping(address => TrustScore) trust_scores;
+
         /// The authorized oracle address that can update scores
         address public oracle_address;
-        
+
         /// Contract owner (can update oracle address)
         address public owner;
-        
+
         /// Minimum score threshold for isTrusted function
         uint16 public trust_threshold;
-        
-        /// Nonce mapping to prevent replay attacks
-        mapping(address => uint256) public nonces;
-    }
-}
 
-#[external]
-impl TrustOracle {
-    /// Initialize the contract with oracle address and trust threshold
-    pub fn init(&mut self, oracle_address: Address, trust_threshold: u16) -> Result<(), Vec<u8>> {
-        // Only allow initialization once
-        if self.owner.get() != Address::ZERO {
-            return Err(b"Already initialized".to_vec());
-        }
-        
-        self.owner.set(msg::sender());
-        self.oracle_address.set(oracle_address);
-        self.trust_threshold.set(trust_threshold);
-        
-        Ok(())
-    }
+        /// Replay nonces of the first release, superseded by `oracle_nonces`; kept so later
+        /// fields keep their slots
+        mapping(address => uint256) nonces;
 
-    /// Update a wallet's trust score with signature verification
-    /// @param wallet The wallet address to update
-    /// @param score The trust score (0-100)
-    /// @param timestamp Unix timestamp of the score computation
-    /// @param metadata_hash Hash of the explanation metadata
-    /// @param signature ECDSA signature from the authorized oracle
-    pub fn update_score(
-        &mut self,
-        wallet: Address,
-        score: u16,
-        timestamp: u32,
-        source: FixedBytes<32>,
-        metadata_hash: FixedBytes<32>,
-        signature: Vec<u8>,
-    ) -> Result<(), Vec<u8>> {
-        // Validate score range
-        if score > 100 {
-            return Err(b"Invalid score range".to_vec());
-        }
+        /// Trust score of each wallet per `ScoreCategory` other than `Aggregate`
+        mapping(address => mapping(uint8 => TrustScore)) trust_scores_by_category;
 
-        // Check timestamp is not stale (within 1 hour)
-        let current_time = evm::block_timestamp();
-        if timestamp > current_time || current_time - timestamp > 3600 {
-            return Err(b"Stale timestamp".to_vec());
-        }
+        /// Oracle waiting for its time lock to expire before replacing `oracle_address`
+        address pending_oracle;
 
-        // Get current nonce for replay protection
-        let nonce = self.nonces.get(wallet);
-        
-        // Create message hash for signature verification
-        let message_hash = self.create_message_hash(wallet, score, timestamp, source, metadata_hash, nonce);
-        
-        // Verify signature
-        if !self.verify_signature(message_hash, signature)? {
-            return Err(b"Invalid signature".to_vec());
-        }
+        /// Earliest block timestamp at which `pending_oracle` can be activated
+        uint64 oracle_activation_time;
 
-        // Update the trust score
-        let trust_score = TrustScore {
-            score,
-            timestamp,
-            source,
-            metadataHash: metadata_hash,
-        };
-        
-        self.trust_scores.setter(wallet).set(trust_score);
-        
-        // Increment nonce to prevent replay
-        self.nonces.setter(wallet).set(nonce + U256::from(1));
+        /// Accounts holding each role (`ORACLE_ROLE`, `ADMIN_ROLE`, `PAUSER_ROLE`)
+        mapping(bytes32 => mapping(address => bool)) roles;
 
-        // Emit event
-        evm::log(ScoreUpdated {
-            wallet,
-            score,
-            timestamp,
-            source,
-            metadataHash: metadata_hash,
-        });
+        /// Proposed owner that must call `accept_ownership` to take over
+        address pending_owner;
 
-        Ok(())
-    }
+        /// Earliest block timestamp at which ownership can be renounced (0 = not proposed)
+        uint64 renounce_at;
 
-    /// Get trust score for a wallet
-    /// @param wallet The wallet address to query
-    /// @return The trust score struct
-    pub fn get_trust_score(&self, wallet: Address) -> TrustScore {
-        self.trust_scores.get(wallet)
-    }
+        /// Set by `init`; a zero owner may be intentional (renounced or DAO-controlled),
+        /// so it cannot double as the initialization guard
+        bool initialized;
 
-    /// Check if a wallet is trusted (score >= threshold)
-    /// @param wallet The wallet address to check
-    /// @return True if wallet is trusted
-    pub fn is_trusted(&self, wallet: Address) -> bool {
-        let score = self.trust_scores.get(wallet).score;
-        score >= self.trust_threshold.get()
-    }
+        /// Replay-protection nonces keyed by (wallet, primary oracle), so rotating the
+        /// oracle starts a fresh nonce space; the retired key keeps its own nonces, usable
+        /// only within the rotation grace window
+        mapping(address => mapping(address => uint256)) oracle_nonces;
 
-    /// Get the current trust threshold
-    pub fn get_trust_threshold(&self) -> u16 {
-        self.trust_threshold.get()
-    }
+        /// keccak256 of the EIP-712 domain version string
+        bytes32 domain_version;
 
-    /// Update the oracle address (owner only)
-    /// @param new_oracle The new oracle address
-    pub fn update_oracle(&mut self, new_oracle: Address) -> Result<(), Vec<u8>> {
-        if msg::sender() != self.owner.get() {
-            return Err(b"Only owner can update oracle".to_vec());
-        }
+        /// EIP-712 domain separator computed once during init
+        bytes32 domain_separator;
 
-        let old_oracle = self.oracle_address.get();
-        self.oracle_address.set(new_oracle);
+        /// Chain id the cached domain separator was computed for
+        uint256 chain_id;
 
-        evm::log(OracleUpdated {
-            oldOracle: old_oracle,
-            newOracle: new_oracle,
-        });
+        /// Voting weight of each registered oracle (zero means not registered)
+        mapping(address => uint16) oracle_registry;
 
-        Ok(())
-    }
+        /// Sum of all registered oracle weights
+        uint256 total_weight;
 
-    /// Update trust threshold (owner only)
-    /// @param new_threshold The new trust threshold
-    pub fn update_trust_threshold(&mut self, new_threshold: u16) -> Result<(), Vec<u8>> {
-        if msg::sender() != self.owner.get() {
-            return Err(b"Only owner can update threshold".to_vec());
-        }
+        /// Percentage of total weight that must sign an update (0 = any single oracle)
+        uint16 quorum_threshold;
 
-        if new_threshold > 100 {
-            return Err(b"Invalid threshold".to_vec());
-        }
+        /// Every oracle with a non-zero weight, so the set can be reset
+        address[] oracle_list;
 
-        self.trust_threshold.set(new_threshold);
-        Ok(())
-    }
-}
+        /// Minimum number of distinct oracles that must sign an update
+        uint16 oracle_quorum;
 
-impl TrustOracle {
-    /// Create message hash for signature verification
-    fn create_message_hash(
-        &self,
-        wallet: Address,
-        score: u16,
-        timestamp: u32,
-        source: FixedBytes<32>,
-        metadata_hash: FixedBytes<32>,
-        nonce: U256,
-    ) -> FixedBytes<32> {
-        use stylus_sdk::crypto::keccak;
+        /// Per-wallet score history, used as a ring buffer of at most `history_depth` entries
+        mapping(address => TrustScore[]) score_history;
+
+        /// Physical index of the oldest entry in each wallet's history buffer
+        mapping(address => uint32) history_head;
+
+        /// Number of history entries kept per wallet (0 disables history)
+        uint8 history_depth;
+
+        /// Seconds after which a stored score is treated as absent (0 = never)
+        uint32 score_ttl_seconds;
+
+        /// When set, all score mutations are rejected
+        bool paused;
+
+        /// Emergency responder that may pause but not unpause or change configuration
+        address guardian;
+
+        /// Maximum accepted score age per source (0 = use the default window)
+        mapping(bytes32 => uint32) source_ttl;
+
+        /// Maximum accepted score age for sources without their own TTL
+        uint32 default_staleness_window;
+
+        /// Share of a score lost per day of age, in basis points (0 = no decay)
+        uint16 decay_rate_per_day;
+
+        /// Score below which decay never pushes a record
+        uint16 decay_floor;
+
+        /// Prefix (e.g. an IPFS gateway path) that `metadataHash` is appended to in `get_metadata_uri`
+        string metadata_base_uri;
+
+        /// Aggregate score each wallet held before its latest update
+        mapping(address => TrustScore) prev_scores;
+
+        /// Per-wallet nonces for `score_permit`, separate from the oracle update nonces
+        mapping(address => uint256) permit_nonces;
+
+        /// Weight of each subcategory in `compute_composite`, in basis points
+        mapping(uint8 => uint16) category_weights;
+
+        /// Latest aggregate score of each wallet as reported by each source
+        mapping(address => mapping(bytes32 => TrustScore)) trust_scores_by_source;
+
+        /// Sources that have reported an aggregate score for each wallet
+        mapping(address => bytes32[]) wallet_sources;
+
+        /// Weight of each source in `get_aggregate_score` (zero excludes the source)
+        mapping(bytes32 => uint16) source_weights;
+
+        /// Merkle root of (wallet, score, confidence, timestamp, source, metadataHash) leaves claimable via `claim_score`
+        bytes32 active_merkle_root;
+
+        /// Last block timestamp at which `active_merkle_root` accepts claims
+        uint32 merkle_root_valid_until;
+
+        /// Replay-protection nonces for Merkle root updates, keyed by primary oracle
+        mapping(address => uint256) merkle_nonces;
+
+        /// Smallest score change accepted over an existing record (0 = any change)
+        uint16 min_score_delta;
+
+        /// Minimum seconds between the timestamps of consecutive updates to a record
+        uint32 min_update_interval;
+
+        /// Wallets whose scores the owner has locked against oracle writes
+        mapping(address => bool) frozen;
+
+        /// Addresses allowed to flag scores as disputed
+        mapping(address => bool) challengers;
+
+        /// Wallets whose scores are disputed and never count as trusted until resolved
+        mapping(address => bool) challenged;
+
+        /// Wallets that never count as trusted, whatever their score
+        mapping(address => bool) blacklisted;
+
+        /// Delegator whose aggregate score is served for each delegated-to wallet
+        mapping(address => address) delegations;
+
+        /// Wallet each delegator currently lends its score to
+        mapping(address => address) delegated_to;
+
+        /// Highest-scoring wallets by aggregate score, best first
+        address[] top_wallets;
+
+        /// Aggregate score of each `top_wallets` entry, kept in the same order
+        uint16[] top_scores;
+
+        /// Number of wallets kept in the leaderboard (0 disables it)
+        uint16 max_leaderboard_size;
+
+        /// Number of wallets per aggregate score percentage of `max_score` (index = percent)
+        uint32[101] score_histogram;
+
+        /// Number of wallets with a stored aggregate score
+        uint32 total_scored_wallets;
+
+        /// Wallets whose aggregate score was trusted when it was last written or revoked
+        uint256 trusted_count;
+
+        /// Human-readable name of each source identifier
+        mapping(bytes32 => string) source_labels;
+
+        /// Gas-paying accounts that submit oracle-signed updates without holding the key
+        mapping(address => bool) trusted_relayers;
+
+        /// Every wallet that has ever received a score, in first-scored order
+        address[] scored_wallets;
+
+        /// Whether a wallet is already listed in `scored_wallets`
+        mapping(address => bool) wallet_indexed;
+
+        /// Upper bound of the score scale, fixed at init (e.g. 100 or 1000)
+        uint16 max_score;
+
+        /// Explicit explanation URI per `metadataHash`, overriding the base-URI form
+        mapping(bytes32 => string) metadata_uris;
+
+        /// Largest score change accepted over an existing record unless the update is forced (0 = unlimited)
+        uint16 max_score_delta;
+
+        /// Whether score updates may only be submitted by registered relayers
+        bool require_allowlisted_relayer;
+
+        /// Replay-protection nonces for multi-wallet batch attestations, keyed by primary oracle
+        mapping(address => uint256) batch_nonces;
+
+        /// ERC-721 attestation contract minted to after each score update (zero = disabled)
+        address score_nft_contract;
+
+        /// Blob-backed aggregate score of each wallet, written by `update_score_v2`
+        mapping(address => TrustScoreV2) trust_scores_v2;
+
+        /// Format of each wallet's latest aggregate score (0 = none, 1 = `TrustScore`, 2 = `TrustScoreV2`)
+        mapping(address => uint8) score_version;
+
+        /// Oracle replaced by the last activation, still accepted for score updates until `rotation_expiry`
+        address previous_oracle;
+
+        /// Last block timestamp at which `previous_oracle` signatures are accepted
+        uint64 rotation_expiry;
+
+        /// ETH each account has staked towards becoming an oracle
+        mapping(address => uint256) staked_amounts;
+
+        /// Stake a proposed oracle must hold before `activate_oracle` or `activate_oracle_weight` succeeds (0 = none)
+        uint256 oracle_staking_requirement;
+
+        /// Earliest block timestamp at which a removed oracle may withdraw its stake
+        mapping(address => uint64) stake_unlock_time;
+
+        /// Whether a wallet currently holds a score, telling "scored 0" apart from "never scored"
+        mapping(address => bool) score_exists;
+
+        /// Baseline aggregate score served for wallets that have never been scored
+        uint16 default_score;
+
+        /// Wallets whose scores were revoked and not rewritten since; never trusted
+        mapping(address => bool) revoked;
+
+        /// Seconds an appeal stays open before it lapses unresolved (0 = until resolved)
+        uint32 appeal_window_seconds;
+
+        /// Hash of the off-chain appeal each wallet filed against its score
+        mapping(address => bytes32) appeal_hashes;
+
+        /// Block timestamp each open appeal was filed at (0 = none)
+        mapping(address => uint64) appeal_filed_at;
+
+        /// Set while a call that may call out (ERC-1271, NFT mint, mirror, ETH transfer) is running
+        bool reentrancy_locked;
+
+        /// Hot-standby oracle every verified score update is copied to (zero = none)
+        address mirror_oracle;
+
+        /// Primary oracle allowed to push copies into this contract via `mirror_score`
+        address mirror_primary;
+
+        /// Position + 1 of each oracle in `oracle_list` (0 = not listed)
+        mapping(address => uint256) oracle_list_index;
+
+        /// Sum of `staked_amounts`, the part of the balance `withdraw` must leave behind
+        uint256 total_staked;
+
+        /// Wei a relayer must attach per score update (0 = updates are free)
+        uint256 update_fee;
+
+        /// Fees paid in since the owner last called `withdraw`
+        uint256 collected_fees;
+
+        /// Whether a wallet is currently included in `trusted_count`
+        mapping(address => bool) trusted_counted;
+
+        /// Earliest block timestamp at which each oracle's proposed weight can be activated (0 = none pending)
+        mapping(address => uint64) oracle_activation_at;
+
+        /// Weight each oracle receives from `activate_oracle_weight`
+        mapping(address => uint16) pending_oracle_weights;
+
+        /// Maximum age in seconds of a submitted update's timestamp
+        uint32 max_timestamp_skew;
+
+        /// Reserved slots so later versions can add fields without shifting this layout
+        uint256[50] storage_gap;
+    }
+}
+
+#[external]
+impl TrustOracle {
+    /// Initialize the contract with oracle address, trust threshold and score scale
+    /// @param max_score Upper bound of the score scale, 0 selects `DEFAULT_MAX_SCORE`
+    /// @param default_score Aggregate score served for never-scored wallets, e.g. a neutral 50
+    pub fn init(
+        &mut self,
+        oracle_address: Address,
+        trust_threshold: u16,
+        max_score: u16,
+        default_score: u16,
+    ) -> Result<(), Vec<u8>> {
+        // Only allow initialization once, whatever the current owner is
+        if self.initialized.get() {
+            return Err(AlreadyInitialized {}.encode());
+        }
         
-        // Create packed message for signing
-        let mut message = Vec::new();
-        message.extend_from_slice(wallet.as_slice());
-        message.extend_from_slice(&score.to_be_bytes());
-        message.extend_from_slice(&timestamp.to_be_bytes());
-        message.extend_from_slice(source.as_slice());
-        message.extend_from_slice(metadata_hash.as_slice());
-        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        self.initialized.set(true);
+        self.owner.set(msg::sender());
+        self.set_role(ADMIN_ROLE, msg::sender(), true);
+        self.set_role(PAUSER_ROLE, msg::sender(), true);
+        self.oracle_address.set(oracle_address);
+        let max_score = if max_score == 0 { DEFAULT_MAX_SCORE } else { max_score };
+        if trust_threshold > max_score {
+            return Err(InvalidThreshold {}.encode());
+        }
+        if default_score > max_score {
+            return Err(InvalidScore {}.encode());
+        }
+        self.max_score.set(max_score);
+        self.trust_threshold.set(trust_threshold);
+        self.default_score.set(default_score);
+        self.domain_version.set(keccak(DOMAIN_VERSION));
+        let separator = self.compute_domain_separator();
+        self.domain_separator.set(separator);
+        self.chain_id.set(U256::from(block::chainid()));
+        self.set_oracle_weight(oracle_address, 1)?;
+        self.oracle_quorum.set(1);
+        self.default_staleness_window.set(DEFAULT_MAX_SCORE_AGE);
+        self.max_timestamp_skew.set(DEFAULT_MAX_TIMESTAMP_SKEW);
+        self.history_depth.set(DEFAULT_HISTORY_DEPTH);
         
-        keccak(message)
+        Ok(())
+    }
+
+    /// Update a wallet's trust score with signature verification
+    /// @param wallet The wallet address to update
+    /// @param category The score category (see `ScoreCategory`)
+    /// @param score The trust score (0 to `max_score`)
+    /// @param confidence How much data backed the score (0-100)
+    /// @param timestamp Unix timestamp of the score computation
+    /// @param metadata_hash Hash of the explanation metadata
+    /// @param deadline Last block timestamp at which the signature may be used
+    /// @param valid_until Last block timestamp at which the score counts as trusted (0 = no expiry)
+    /// @param oracle The oracle that signed; must be `oracle_address` when a trusted relayer
+    ///        submits, ignored when the oracle submits itself
+    /// @param signature ECDSA signature from an authorized oracle
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn update_score(
+        &mut self,
+        wallet: Address,
+        category: u8,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        deadline: u32,
+        valid_until: u32,
+        oracle: Address,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_submitter_for(oracle)?;
+
+        let update = ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline,
+            valid_until,
+            force: false,
+        };
+        self.lock()?;
+        self.charge_update_fee(1)?;
+        self.apply_score_update(update, vec![signature])?;
+        self.unlock();
+        Ok(())
+    }
+
+    /// Update a wallet's trust score in the category named by a bytes32 tag
+    /// @param category `ScoreCategory::tag` of the category, zero for `DEFAULT_CATEGORY`
+    ///
+    /// Same as `update_score` with the tag's `u8` category; the oracle signs that `u8` value.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn update_score_for_category(
+        &mut self,
+        wallet: Address,
+        category: FixedBytes<32>,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        deadline: u32,
+        valid_until: u32,
+        oracle: Address,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        let category = ScoreCategory::from_tag(category).ok_or_else(|| InvalidCategory {}.encode())?;
+        self.update_score(
+            wallet,
+            category as u8,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline,
+            valid_until,
+            oracle,
+            signature,
+        )
+    }
+
+    /// Update a wallet's trust score past the `max_score_delta` circuit breaker
+    /// @param signature ECDSA signature from an authorized oracle over the update with `force = true`
+    ///
+    /// Identical to `update_score` except that the oracle signs the force flag, so a
+    /// deliberate large correction goes through while an ordinary signature cannot.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn force_update_score(
+        &mut self,
+        wallet: Address,
+        category: u8,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        deadline: u32,
+        valid_until: u32,
+        oracle: Address,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_submitter_for(oracle)?;
+
+        let update = ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline,
+            valid_until,
+            force: true,
+        };
+        self.lock()?;
+        self.charge_update_fee(1)?;
+        self.apply_score_update(update, vec![signature])?;
+        self.unlock();
+        Ok(())
+    }
+
+    /// Update a wallet's blob-backed aggregate score
+    /// @param wallet The wallet to update
+    /// @param score The trust score (0 to `max_score`)
+    /// @param confidence How much data backed the score (0-100)
+    /// @param timestamp Unix timestamp of the score computation
+    /// @param source Source identifier
+    /// @param blob_versioned_hash EIP-4844 versioned hash of the blob carrying the explanation
+    /// @param deadline Last block timestamp at which the signature may be used
+    /// @param signature Oracle signature over `TrustScoreV2(wallet, score, confidence, timestamp, source, blobVersionedHash, deadline, oracle, nonce)`
+    ///
+    /// Writes only the v2 slot read by `get_trust_score_v2`; `get_trust_score` keeps
+    /// serving the v1 record. Shares the wallet's nonce with `update_score`.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn update_score_v2(
+        &mut self,
+        wallet: Address,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        blob_versioned_hash: FixedBytes<32>,
+        deadline: u32,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.when_not_paused()?;
+
+        self.only_allowlisted_relayer()?;
+        self.lock()?;
+        self.charge_update_fee(1)?;
+
+        check_score_range(score, confidence, self.max_score.get())?;
+
+        if blob_versioned_hash[0] != BLOB_COMMITMENT_VERSION_KZG {
+            return Err(InvalidBlobHash {}.encode());
+        }
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        let current_time = evm::block_timestamp();
+        if timestamp > current_time || current_time - timestamp > self.update_window(source) {
+            return Err(StaleTimestamp {}.encode());
+        }
+
+        if current_time > deadline {
+            return Err(SignatureExpired {}.encode());
+        }
+
+        if timestamp <= self.trust_scores_v2.get(wallet).timestamp {
+            return Err(NonMonotonicTimestamp {}.encode());
+        }
+
+        let oracle = self.oracle_address.get();
+        let nonce = self.oracle_nonces.get(wallet).get(oracle);
+
+        let mut encoded = Vec::with_capacity(320);
+        encoded.extend_from_slice(TRUST_SCORE_V2_TYPEHASH.as_slice());
+        push_address(&mut encoded, wallet);
+        push_uint(&mut encoded, U256::from(score));
+        push_uint(&mut encoded, U256::from(confidence));
+        push_uint(&mut encoded, U256::from(timestamp));
+        encoded.extend_from_slice(source.as_slice());
+        encoded.extend_from_slice(blob_versioned_hash.as_slice());
+        push_uint(&mut encoded, U256::from(deadline));
+        push_address(&mut encoded, oracle);
+        push_uint(&mut encoded, nonce);
+        let message_hash = self.typed_data_digest(keccak(encoded));
+
+        self.verify_quorum(message_hash, vec![signature])?;
+
+        self.trust_scores_v2.setter(wallet).set(TrustScoreV2 {
+            score,
+            confidence,
+            timestamp,
+            source,
+            blobVersionedHash: blob_versioned_hash,
+        });
+        self.score_version.setter(wallet).set(2);
+        self.score_exists.setter(wallet).set(true);
+        self.revoked.setter(wallet).set(false);
+        self.oracle_nonces
+            .setter(wallet)
+            .setter(oracle)
+            .set(nonce + U256::from(1));
+
+        evm::log(ScoreUpdatedV2 {
+            wallet,
+            score,
+            confidence,
+            timestamp,
+            source,
+            blobVersionedHash: blob_versioned_hash,
+        });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Update a wallet's trust score with signatures from several oracles (M-of-N)
+    /// @param oracle Must be `oracle_address` when a trusted relayer submits, ignored when
+    ///        an oracle submits itself
+    /// @param signatures One ECDSA signature per distinct authorized oracle
+    ///
+    /// At least `oracle_quorum` distinct oracles holding the weight quorum must sign;
+    /// duplicate signers and signatures from unauthorized keys revert the call.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn update_score_multi(
+        &mut self,
+        wallet: Address,
+        category: u8,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        deadline: u32,
+        valid_until: u32,
+        oracle: Address,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_submitter_for(oracle)?;
+
+        let update = ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline,
+            valid_until,
+            force: false,
+        };
+        self.lock()?;
+        self.charge_update_fee(1)?;
+        self.apply_score_update(update, signatures)?;
+        self.unlock();
+        Ok(())
+    }
+
+    /// Update many wallets' trust scores in a single transaction
+    /// @param updates (wallet, category, score, confidence, timestamp, source, metadata_hash, deadline, valid_until, signature) entries
+    /// @param oracle The oracle that signed the entries; must be `oracle_address` when a
+    ///        trusted relayer submits, ignored when the oracle submits itself
+    ///
+    /// Every entry is checked exactly like `update_score`; the first failing entry
+    /// reverts the whole batch so no partial set of scores is ever committed.
+    #[payable]
+    pub fn batch_update_scores(
+        &mut self,
+        updates: Vec<(Address, u8, u16, u16, u32, FixedBytes<32>, FixedBytes<32>, u32, u32, Vec<u8>)>,
+        oracle: Address,
+    ) -> Result<(), Vec<u8>> {
+        self.only_submitter_for(oracle)?;
+        self.lock()?;
+        self.charge_update_fee(updates.len())?;
+        for (wallet, category, score, confidence, timestamp, source, metadata_hash, deadline, valid_until, signature) in updates {
+            let update = ScoreUpdate {
+                wallet,
+                category,
+                score,
+                confidence,
+                timestamp,
+                source,
+                metadata_hash,
+                deadline,
+                valid_until,
+                force: false,
+            };
+            self.apply_score_update(update, vec![signature])?;
+        }
+        self.unlock();
+
+        Ok(())
+    }
+
+    /// Update many wallets' trust scores, skipping entries that fail
+    /// @param wallets Wallet addresses to update
+    /// @param categories Score category per entry
+    /// @param scores Trust score per entry
+    /// @param confidences Score confidence per entry
+    /// @param timestamps Score computation timestamp per entry
+    /// @param sources Source identifier per entry
+    /// @param metadata_hashes Explanation metadata hash per entry
+    /// @param deadlines Signature deadline per entry
+    /// @param valid_untils Trust expiry per entry (0 = none)
+    /// @param oracle The oracle that signed the entries; must be `oracle_address` when a
+    ///        trusted relayer submits, ignored when the oracle submits itself
+    /// @param signatures Oracle signature per entry
+    /// @return Number of entries that were applied
+    ///
+    /// Unlike `batch_update_scores`, a bad entry does not revert the batch: it is
+    /// skipped, a `BatchEntryFailed` event records its index and revert reason, and
+    /// its wallet's nonce is left untouched. Only a paused contract or mismatched
+    /// array lengths revert.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn update_scores_batch(
+        &mut self,
+        wallets: Vec<Address>,
+        categories: Vec<u8>,
+        scores: Vec<u16>,
+        confidences: Vec<u16>,
+        timestamps: Vec<u32>,
+        sources: Vec<FixedBytes<32>>,
+        metadata_hashes: Vec<FixedBytes<32>>,
+        deadlines: Vec<u32>,
+        valid_untils: Vec<u32>,
+        oracle: Address,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<u32, Vec<u8>> {
+        // Pausing or an unlisted caller must stop the batch outright rather than skip every entry
+        self.when_not_paused()?;
+
+        self.only_allowlisted_relayer()?;
+        self.only_submitter_for(oracle)?;
+        self.lock()?;
+
+        let len = wallets.len();
+        if categories.len() != len
+            || scores.len() != len
+            || confidences.len() != len
+            || timestamps.len() != len
+            || sources.len() != len
+            || metadata_hashes.len() != len
+            || deadlines.len() != len
+            || valid_untils.len() != len
+            || signatures.len() != len
+        {
+            return Err(BatchLengthMismatch {}.encode());
+        }
+        self.charge_update_fee(len)?;
+
+        let entries = signatures.into_iter().enumerate().map(|(index, signature)| {
+            let update = ScoreUpdate {
+                wallet: wallets[index],
+                category: categories[index],
+                score: scores[index],
+                confidence: confidences[index],
+                timestamp: timestamps[index],
+                source: sources[index],
+                metadata_hash: metadata_hashes[index],
+                deadline: deadlines[index],
+                valid_until: valid_untils[index],
+                force: false,
+            };
+            (update, signature)
+        });
+        let (applied, failures) =
+            apply_each(entries, |(update, signature)| self.apply_score_update(update, vec![signature]));
+
+        for (index, reason) in failures {
+            evm::log(BatchEntryFailed {
+                index: U256::from(index),
+                wallet: wallets[index],
+                reason: reason.into(),
+            });
+        }
+
+        self.unlock();
+        Ok(applied)
+    }
+
+    /// Write aggregate scores for many wallets under a single oracle signature
+    /// @param wallets Wallets being attested
+    /// @param scores Trust score per wallet
+    /// @param confidences Score confidence per wallet
+    /// @param timestamp Unix timestamp of the score computation, shared by the batch
+    /// @param source Source identifier, shared by the batch
+    /// @param metadata_hash Hash of the explanation metadata, shared by the batch
+    /// @param deadline Last block timestamp at which the signature may be used
+    /// @param signature Oracle signature over `ScoreBatch(wallets, scores, confidences, timestamp, source, metadataHash, deadline, oracle, nonce)`
+    ///
+    /// Replay protection is per batch: the signature binds the whole list and the
+    /// oracle's batch nonce, which is bumped once on success. Per-wallet nonces are
+    /// untouched. Every entry is checked like `update_score` and any failure reverts
+    /// the whole batch.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn attest_batch(
+        &mut self,
+        wallets: Vec<Address>,
+        scores: Vec<u16>,
+        confidences: Vec<u16>,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        deadline: u32,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.when_not_paused()?;
+
+        self.only_allowlisted_relayer()?;
+        self.lock()?;
+
+        if scores.len() != wallets.len() || confidences.len() != wallets.len() {
+            return Err(BatchLengthMismatch {}.encode());
+        }
+        self.charge_update_fee(wallets.len())?;
+
+        let oracle = self.oracle_address.get();
+        let nonce = self.batch_nonces.get(oracle);
+        let struct_hash = score_batch_struct_hash(
+            &wallets,
+            &scores,
+            &confidences,
+            (timestamp, source, metadata_hash, deadline),
+            oracle,
+            nonce,
+        );
+        let message_hash = self.typed_data_digest(struct_hash);
+
+        self.verify_quorum(message_hash, vec![signature])?;
+
+        let category = ScoreCategory::Aggregate as u8;
+        for ((&wallet, &score), &confidence) in wallets.iter().zip(&scores).zip(&confidences) {
+            let update = ScoreUpdate {
+                wallet,
+                category,
+                score,
+                confidence,
+                timestamp,
+                source,
+                metadata_hash,
+                deadline,
+                valid_until: 0,
+                force: false,
+            };
+            self.validate_score_update(&update)?;
+
+            let trust_score = TrustScore {
+                score,
+                confidence,
+                timestamp,
+                validUntil: 0,
+                source,
+                metadataHash: metadata_hash,
+            };
+            self.store_score(wallet, category, trust_score)?;
+
+            evm::log(ScoreUpdated {
+                wallet,
+                category,
+                score,
+                confidence,
+                timestamp,
+                source,
+                metadataHash: metadata_hash,
+                deadline,
+                validUntil: 0,
+            });
+        }
+
+        self.batch_nonces.setter(oracle).set(nonce + U256::from(1));
+
+        evm::log(BatchAttested {
+            oracle,
+            nonce,
+            count: wallets.len() as u32,
+        });
+
+        for &wallet in &wallets {
+            self.forward_to_mirror(wallet, ScoreCategory::Aggregate as u8);
+        }
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Get the nonce the next batch attestation must use under a given primary oracle
+    pub fn get_batch_nonce(&self, oracle: Address) -> U256 {
+        self.batch_nonces.get(oracle)
     }
 
-    /// Verify ECDSA signature
-    fn verify_signature(&self, message_hash: FixedBytes<32>, signature: Vec<u8>) -> Result<bool, Vec<u8>> {
-        if signature.len() != 65 {
-            return Ok(false);
+    /// Publish a Merkle root of aggregate scores that wallets can claim individually
+    /// @param root Root over `keccak256(keccak256(abi.encode(wallet, score, confidence, timestamp, source, metadataHash)))` leaves
+    /// @param valid_until Last block timestamp at which claims against `root` are accepted
+    /// @param signature Oracle signature over `MerkleRoot(root, validUntil, oracle, nonce)`
+    ///
+    /// One signature covers an arbitrarily large batch; the gas of writing each score is
+    /// paid by whoever submits its proof through `claim_score`.
+    pub fn update_merkle_root(
+        &mut self,
+        root: FixedBytes<32>,
+        valid_until: u32,
+        signature: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        self.when_not_paused()?;
+
+        if evm::block_timestamp() > valid_until {
+            return Err(SignatureExpired {}.encode());
         }
 
-        // Extract r, s, v from signature
-        let mut r = [0u8; 32];
-        let mut s = [0u8; 32];
-        r.copy_from_slice(&signature[0..32]);
-        s.copy_from_slice(&signature[32..64]);
-        let v = signature[64];
+        let oracle = self.oracle_address.get();
+        let nonce = self.merkle_nonces.get(oracle);
+
+        let mut encoded = Vec::with_capacity(160);
+        encoded.extend_from_slice(MERKLE_ROOT_TYPEHASH.as_slice());
+        encoded.extend_from_slice(root.as_slice());
+        push_uint(&mut encoded, U256::from(valid_until));
+        push_address(&mut encoded, oracle);
+        push_uint(&mut encoded, nonce);
+        let message_hash = self.typed_data_digest(keccak(encoded));
+
+        self.lock()?;
+        self.verify_quorum(message_hash, vec![signature])?;
+
+        self.active_merkle_root.set(root);
+        self.merkle_root_valid_until.set(valid_until);
+        self.merkle_nonces.setter(oracle).set(nonce + U256::from(1));
+
+        evm::log(MerkleRootUpdated {
+            root,
+            validUntil: valid_until,
+            oracle,
+        });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Write a wallet's aggregate score by proving it is a leaf of the active Merkle root (anyone)
+    /// @param wallet The wallet the leaf belongs to
+    /// @param score The trust score (0 to `max_score`)
+    /// @param confidence How much data backed the score (0-100)
+    /// @param timestamp Unix timestamp of the score computation
+    /// @param source Source identifier
+    /// @param metadata_hash Hash of the explanation metadata
+    /// @param proof Sibling hashes from the leaf up to the root, pairs hashed in sorted order
+    ///
+    /// A claim goes through the same checks as a signed `update_score`: it must be newer
+    /// than the wallet's stored score, so an old leaf cannot be replayed to roll a score
+    /// back, and it pays the same fee. The root's expiry stands in for the signature deadline.
+    #[allow(clippy::too_many_arguments)]
+    #[payable]
+    pub fn claim_score(
+        &mut self,
+        wallet: Address,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+        proof: Vec<FixedBytes<32>>,
+    ) -> Result<(), Vec<u8>> {
+        self.lock()?;
+        self.charge_update_fee(1)?;
+
+        self.when_not_paused()?;
+
+        self.only_allowlisted_relayer()?;
+
+        let root = self.active_merkle_root.get();
+        let valid_until = self.merkle_root_valid_until.get();
+        if root == FixedBytes::ZERO || evm::block_timestamp() > valid_until {
+            return Err(MerkleRootExpired {}.encode());
+        }
+
+        let category = ScoreCategory::Aggregate as u8;
+        self.validate_score_update(&ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline: valid_until,
+            valid_until: 0,
+            force: false,
+        })?;
+
+        let leaf = merkle_leaf(wallet, score, confidence, timestamp, source, metadata_hash);
+        if process_proof(leaf, &proof) != root {
+            return Err(InvalidMerkleProof {}.encode());
+        }
+
+        let trust_score = TrustScore {
+            score,
+            confidence,
+            timestamp,
+            validUntil: 0,
+            source,
+            metadataHash: metadata_hash,
+        };
+        self.store_score(wallet, category, trust_score)?;
+
+        // A claim carries no signature, so there is no deadline to report
+        evm::log(ScoreUpdated {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadataHash: metadata_hash,
+            deadline: 0,
+            validUntil: 0,
+        });
+
+        self.forward_to_mirror(wallet, category);
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Get the active Merkle root and the last timestamp it accepts claims
+    pub fn get_merkle_root(&self) -> (FixedBytes<32>, u32) {
+        (self.active_merkle_root.get(), self.merkle_root_valid_until.get())
+    }
+
+    /// Get the nonce the next Merkle root signature must use under a given primary oracle
+    pub fn get_merkle_nonce(&self, oracle: Address) -> U256 {
+        self.merkle_nonces.get(oracle)
+    }
+
+    /// Delete every category score of a wallet
+    /// @param wallet The wallet whose scores are revoked
+    /// @param signature Oracle signature over `RevokeScore(wallet, oracle, nonce)`
+    ///
+    /// Uses the same nonce as score updates, so a revocation cannot be replayed and
+    /// any update signed before it becomes invalid.
+    pub fn revoke_score(&mut self, wallet: Address, signature: Vec<u8>) -> Result<(), Vec<u8>> {
+        self.lock()?;
+        self.apply_revocation(wallet, signature)?;
+        self.unlock();
+        Ok(())
+    }
+
+    /// Delete every category score of a wallet, recording why (authorized oracles only)
+    /// @param wallet The wallet whose scores are revoked
+    /// @param reason_hash Hash of the off-chain explanation for the revocation
+    ///
+    /// Like `revoke_score` but authorized by the caller instead of a signature. The
+    /// wallet reads as revoked until its next score update.
+    pub fn revoke_score_with_reason(&mut self, wallet: Address, reason_hash: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        self.lock()?;
+
+        if !self.has_role(ORACLE_ROLE, msg::sender()) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        self.when_not_paused()?;
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        // Invalidate updates signed before the revocation, as the signed path does
+        let oracle = self.oracle_address.get();
+        let nonce = self.oracle_nonces.get(wallet).get(oracle);
+        self.oracle_nonces
+            .setter(wallet)
+            .setter(oracle)
+            .set(nonce + U256::from(1));
+
+        self.clear_scores(wallet);
+
+        evm::log(ScoreRevoked {
+            wallet,
+            oracle: msg::sender(),
+            reason_hash,
+            revoked_at: evm::block_timestamp(),
+        });
+
+        self.forward_revocation_to_mirror(wallet, reason_hash);
+        self.unlock();
+        Ok(())
+    }
+
+    /// Check whether a wallet's scores were revoked and not rewritten since
+    pub fn is_revoked(&self, wallet: Address) -> bool {
+        self.revoked.get(wallet)
+    }
+
+    /// Revoke many wallets' scores, skipping entries that fail
+    /// @param wallets Wallets whose scores are revoked
+    /// @param signatures Oracle `RevokeScore` signature per wallet
+    /// @return Number of wallets that were revoked
+    ///
+    /// Each entry is checked exactly like `revoke_score`. A bad entry is skipped and
+    /// reported through `BatchEntryFailed`; only a paused contract or mismatched
+    /// array lengths revert.
+    pub fn revoke_scores_batch(&mut self, wallets: Vec<Address>, signatures: Vec<Vec<u8>>) -> Result<u32, Vec<u8>> {
+        self.when_not_paused()?;
+
+        if signatures.len() != wallets.len() {
+            return Err(BatchLengthMismatch {}.encode());
+        }
+
+        self.lock()?;
+        let entries = wallets.iter().copied().zip(signatures);
+        let (revoked, failures) =
+            apply_each(entries, |(wallet, signature)| self.apply_revocation(wallet, signature));
+
+        for (index, reason) in failures {
+            evm::log(BatchEntryFailed {
+                index: U256::from(index),
+                wallet: wallets[index],
+                reason: reason.into(),
+            });
+        }
+
+        self.unlock();
+        Ok(revoked)
+    }
+
+    /// Rebuild a wallet's aggregate score from its subcategory scores (anyone)
+    /// @param wallet The wallet to recompute
+    /// @return The new aggregate score
+    ///
+    /// Takes the `category_weights`-weighted average score and confidence of the live DeFi, NFT, DAO,
+    /// Social and Gaming scores and stores it under `COMPOSITE_SOURCE`. The composite is
+    /// timestamped with its oldest input and expires with its earliest-expiring one, and
+    /// must be newer than the stored aggregate, so recomputing unchanged inputs reverts.
+    pub fn compute_composite(&mut self, wallet: Address) -> Result<u16, Vec<u8>> {
+        self.lock()?;
+        self.when_not_paused()?;
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        let entries: Vec<(u16, TrustScore)> = ScoreCategory::ALL
+            .into_iter()
+            .filter(|&category| category != ScoreCategory::Aggregate)
+            .map(|category| {
+                (
+                    self.category_weights.get(category as u8),
+                    self.get_trust_score_by_category(wallet, category as u8),
+                )
+            })
+            .collect();
+        let composite = weighted_blend(&entries, evm::block_timestamp())
+            .ok_or_else(|| NoCategoryScores {}.encode())?;
+
+        let category = ScoreCategory::Aggregate as u8;
+        let stored = self.read_score(wallet, category);
+        if composite.timestamp <= stored.timestamp {
+            return Err(NonMonotonicTimestamp {}.encode());
+        }
+
+        let (score, timestamp) = (composite.score, composite.timestamp);
+        let trust_score = TrustScore {
+            source: COMPOSITE_SOURCE,
+            ..composite
+        };
+        self.store_score(wallet, category, trust_score)?;
+
+        evm::log(CompositeComputed {
+            wallet,
+            new_score: score,
+            timestamp,
+        });
+
+        self.forward_to_mirror(wallet, category);
+
+        self.unlock();
+        Ok(score)
+    }
+
+    /// Get the aggregate score a single source last reported for a wallet
+    /// @param wallet The wallet address to query
+    /// @param source The source identifier
+    /// @return The stored score, or a zeroed struct if absent or expired
+    pub fn get_trust_score_by_source(&self, wallet: Address, source: FixedBytes<32>) -> TrustScore {
+        let trust_score = self.trust_scores_by_source.get(wallet).get(source);
+        if self.is_expired(&trust_score) {
+            return empty_score();
+        }
+        trust_score
+    }
+
+    /// Get the weighted average of a wallet's unexpired per-source scores
+    /// @param wallet The wallet address to query
+    /// @return The average weighted by `source_weights`, 0 if no weighted source has a score
+    pub fn get_aggregate_score(&self, wallet: Address) -> u16 {
+        self.blend_sources(wallet).map_or(0, |blended| blended.score)
+    }
+
+    /// Write the `source_weights` blend of a wallet's per-source scores to its aggregate slot (anyone)
+    /// @param wallet The wallet to blend
+    /// @return The new aggregate score
+    ///
+    /// Lets an integration refresh the aggregate right before a critical transaction.
+    /// The result is stored under `BLEND_SOURCE` with the same weighting applied to
+    /// confidence. Like `compute_composite`, it is timestamped with its oldest input,
+    /// expires with its earliest-expiring one and must be newer than the stored aggregate.
+    pub fn blend_scores(&mut self, wallet: Address) -> Result<u16, Vec<u8>> {
+        self.lock()?;
+        self.when_not_paused()?;
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        let blended = self
+            .blend_sources(wallet)
+            .ok_or_else(|| NoSourceScores {}.encode())?;
+
+        let category = ScoreCategory::Aggregate as u8;
+        let stored = self.read_score(wallet, category);
+        if blended.timestamp <= stored.timestamp {
+            return Err(NonMonotonicTimestamp {}.encode());
+        }
+
+        let (score, timestamp) = (blended.score, blended.timestamp);
+        let trust_score = TrustScore {
+            source: BLEND_SOURCE,
+            ..blended
+        };
+        self.store_score(wallet, category, trust_score)?;
+
+        evm::log(ScoresBlended {
+            wallet,
+            new_score: score,
+            timestamp,
+        });
+
+        self.forward_to_mirror(wallet, category);
+
+        self.unlock();
+        Ok(score)
+    }
+
+    /// Get the sources that have reported an aggregate score for a wallet
+    pub fn get_wallet_sources(&self, wallet: Address) -> Vec<FixedBytes<32>> {
+        let sources = self.wallet_sources.get(wallet);
+        (0..sources.len()).filter_map(|i| sources.get(i)).collect()
+    }
+
+    /// Get the aggregate trust score for a wallet
+    /// @param wallet The wallet address to query
+    /// @return The trust score struct, taken from the delegator if `wallet` received a delegation;
+    /// `default_score` with a zero timestamp if the wallet was never scored
+    pub fn get_trust_score(&self, wallet: Address) -> TrustScore {
+        let holder = self.score_holder(wallet);
+        let trust_score = self.get_trust_score_by_category(holder, ScoreCategory::Aggregate as u8);
+        let defaulted = self.is_defaulted(holder);
+        score_or_default(trust_score, defaulted, self.default_score.get())
+    }
+
+    /// Get the oracle-signed expiry of a wallet's aggregate score
+    /// @param wallet The wallet address to query
+    /// @return Last block timestamp at which `is_trusted` can hold, 0 if the score never expires
+    pub fn get_valid_until(&self, wallet: Address) -> u32 {
+        self.get_trust_score(wallet).validUntil
+    }
+
+    /// Get a wallet's blob-backed aggregate score written by `update_score_v2`
+    /// @param wallet The wallet address to query
+    /// @return The v2 record, all zeros if absent or older than `score_ttl_seconds`
+    pub fn get_trust_score_v2(&self, wallet: Address) -> TrustScoreV2 {
+        let trust_score = self.trust_scores_v2.get(wallet);
+        let ttl = self.score_ttl_seconds.get();
+        if ttl != 0 && evm::block_timestamp().saturating_sub(trust_score.timestamp) > ttl {
+            return empty_score_v2();
+        }
+        trust_score
+    }
+
+    /// Get the format of a wallet's latest aggregate score
+    /// @return 0 if never scored, 1 for `TrustScore`, 2 for `TrustScoreV2`
+    pub fn get_score_version(&self, wallet: Address) -> u8 {
+        self.score_version.get(wallet)
+    }
+
+    /// Get the aggregate trust score for a wallet together with its source's label
+    /// @param wallet The wallet address to query
+    /// @return The `get_trust_score` result and the label of its source (empty if unregistered)
+    pub fn get_trust_score_labeled(&self, wallet: Address) -> (TrustScore, String) {
+        let trust_score = self.get_trust_score(wallet);
+        let label = self.get_source_label(trust_score.source);
+        (trust_score, label)
+    }
+
+    /// Get a wallet's aggregate score, reverting unless it is at most `max_age` seconds old
+    /// @param _consumer The integrating contract, usually `address(this)`; informational only
+    /// @param wallet The wallet address to query
+    /// @param max_age Freshness the caller requires, independent of the staleness window
+    /// @return The `get_trust_score` result; reverts with `StaleTimestamp` if stale or unscored
+    ///
+    /// Lets integrators enforce their own freshness rule and bubble up a typed revert.
+    pub fn require_fresh_score_for(
+        &self,
+        _consumer: Address,
+        wallet: Address,
+        max_age: u32,
+    ) -> Result<TrustScore, Vec<u8>> {
+        let trust_score = self.get_trust_score(wallet);
+        if trust_score.timestamp == 0
+            || evm::block_timestamp().saturating_sub(trust_score.timestamp) > max_age
+        {
+            return Err(StaleTimestamp {}.encode());
+        }
+
+        Ok(trust_score)
+    }
+
+    /// Revert with `WalletNotTrusted(wallet)` unless `is_trusted(wallet)` holds
+    /// @param wallet The wallet address to check
+    ///
+    /// Lets integrators gate a call with one static call and a decodable revert.
+    pub fn require_trusted(&self, wallet: Address) -> Result<(), Vec<u8>> {
+        if !self.is_trusted(wallet) {
+            return Err(WalletNotTrusted { wallet }.encode());
+        }
+        Ok(())
+    }
+
+    /// Check whether a wallet holds a score, so "unscored" can be told apart from "scored 0"
+    /// @param wallet The wallet address to query
+    /// @return True from the wallet's first score write until its scores are revoked
+    pub fn has_score(&self, wallet: Address) -> bool {
+        self.score_exists.get(wallet)
+    }
+
+    /// Check if a wallet's aggregate score meets the threshold
+    /// @param wallet The wallet address to check
+    /// @return True if wallet (or its delegator) is trusted; always false for a blacklisted wallet
+    pub fn is_trusted(&self, wallet: Address) -> bool {
+        if self.blacklisted.get(wallet) {
+            return false;
+        }
+
+        let holder = self.score_holder(wallet);
+        if self.is_defaulted(holder) {
+            return !self.challenged.get(holder) && self.default_score.get() >= self.trust_threshold.get();
+        }
+        self.is_trusted_for_category(holder, ScoreCategory::Aggregate as u8)
+    }
+
+    /// Let `to` be served the caller's aggregate score
+    /// @param to The wallet that will inherit the caller's score on reads
+    ///
+    /// Only reads follow a delegation; updates keep writing to the wallet they name.
+    pub fn delegate_score(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        let from = msg::sender();
+        if to == Address::ZERO || to == from {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        if self.delegations.get(to) != Address::ZERO || self.delegated_to.get(from) != Address::ZERO {
+            return Err(DelegationExists {}.encode());
+        }
+
+        self.delegations.setter(to).set(from);
+        self.delegated_to.setter(from).set(to);
+
+        evm::log(DelegationGranted { from, to });
+
+        Ok(())
+    }
+
+    /// Withdraw the caller's score delegation
+    pub fn revoke_delegation(&mut self) -> Result<(), Vec<u8>> {
+        let from = msg::sender();
+        let to = self.delegated_to.get(from);
+        if to == Address::ZERO {
+            return Err(NoDelegation {}.encode());
+        }
+
+        self.delegations.setter(to).set(Address::ZERO);
+        self.delegated_to.setter(from).set(Address::ZERO);
+
+        evm::log(DelegationRevoked { from, to });
+
+        Ok(())
+    }
+
+    /// Get the wallet whose score is served for `wallet` (zero if none)
+    pub fn get_delegator(&self, wallet: Address) -> Address {
+        self.delegations.get(wallet)
+    }
+
+    /// Get the aggregate trust scores of many wallets in one call
+    /// @param wallets Up to `MAX_BATCH_READ` wallet addresses
+    /// @return Scores in the same order as `wallets`
+    pub fn get_trust_scores(&self, wallets: Vec<Address>) -> Result<Vec<TrustScore>, Vec<u8>> {
+        self.get_trust_score_batch(wallets)
+    }
+
+    /// Check many wallets against the trust threshold in one call
+    /// @param wallets Up to `MAX_BATCH_READ` wallet addresses
+    /// @return `is_trusted` results in the same order as `wallets`
+    pub fn are_trusted(&self, wallets: Vec<Address>) -> Result<Vec<bool>, Vec<u8>> {
+        self.is_trusted_batch(wallets)
+    }
+
+    /// Get the aggregate trust scores of many wallets in a single `eth_call`
+    /// @param wallets Up to `MAX_BATCH_READ` wallet addresses
+    /// @return Scores in the same order as `wallets`
+    ///
+    /// Meant for off-chain reads; on-chain callers pay gas per wallet and should keep
+    /// batches well below the cap to stay inside the block gas limit.
+    pub fn get_trust_score_batch(&self, wallets: Vec<Address>) -> Result<Vec<TrustScore>, Vec<u8>> {
+        if wallets.len() > MAX_BATCH_READ {
+            return Err(BatchTooLarge {}.encode());
+        }
+
+        Ok(wallets.into_iter().map(|wallet| self.get_trust_score(wallet)).collect())
+    }
+
+    /// Check many wallets against the trust threshold in a single `eth_call`
+    /// @param wallets Up to `MAX_BATCH_READ` wallet addresses
+    /// @return `is_trusted` results in the same order as `wallets`
+    ///
+    /// Subject to the same on-chain gas caveat as `get_trust_score_batch`.
+    pub fn is_trusted_batch(&self, wallets: Vec<Address>) -> Result<Vec<bool>, Vec<u8>> {
+        if wallets.len() > MAX_BATCH_READ {
+            return Err(BatchTooLarge {}.encode());
+        }
+
+        Ok(wallets.into_iter().map(|wallet| self.is_trusted(wallet)).collect())
+    }
+
+    /// Disclose a wallet's aggregate score to a requester the wallet signed a permit for
+    /// @param wallet The wallet whose score is disclosed
+    /// @param requester The relayer allowed to use the permit, must be the caller
+    /// @param deadline Last block timestamp at which the permit may be used
+    /// @param signature Wallet signature over `ScorePermit(wallet, requester, nonce, deadline)`
+    /// @return The wallet's aggregate trust score
+    ///
+    /// Scores are not modified; the consumed nonce and `ScorePermitUsed` event are the
+    /// on-chain record that the wallet consented to the disclosure.
+    pub fn score_permit(
+        &mut self,
+        wallet: Address,
+        requester: Address,
+        deadline: u64,
+        signature: Vec<u8>,
+    ) -> Result<TrustScore, Vec<u8>> {
+        if msg::sender() != requester {
+            return Err(NotPermitRequester {}.encode());
+        }
+
+        if block::timestamp() > deadline {
+            return Err(SignatureExpired {}.encode());
+        }
+
+        let nonce = self.permit_nonces.get(wallet);
+
+        let mut encoded = Vec::with_capacity(160);
+        encoded.extend_from_slice(SCORE_PERMIT_TYPEHASH.as_slice());
+        push_address(&mut encoded, wallet);
+        push_address(&mut encoded, requester);
+        push_uint(&mut encoded, nonce);
+        push_uint(&mut encoded, U256::from(deadline));
+        let message_hash = self.typed_data_digest(keccak(encoded));
+
+        if self.recover_signer(message_hash, &signature) != Some(wallet) {
+            return Err(InvalidSignature {}.encode());
+        }
+
+        self.permit_nonces.setter(wallet).set(nonce + U256::from(1));
+
+        evm::log(ScorePermitUsed {
+            wallet,
+            requester,
+            nonce,
+        });
+
+        Ok(self.get_trust_score(wallet))
+    }
+
+    /// Get the nonce the next `score_permit` signature of a wallet must use
+    pub fn get_permit_nonce(&self, wallet: Address) -> U256 {
+        self.permit_nonces.get(wallet)
+    }
+
+    /// Get a wallet's trust score in one category
+    /// @param wallet The wallet address to query
+    /// @param category The score category (see `ScoreCategory`)
+    /// @return The stored score, or a zeroed struct once it has expired
+    pub fn get_trust_score_by_category(&self, wallet: Address, category: u8) -> TrustScore {
+        let trust_score = self.read_score(wallet, category);
+        if self.is_expired(&trust_score) {
+            return empty_score();
+        }
+        trust_score
+    }
+
+    /// Get a wallet's trust score in the category named by a bytes32 tag
+    /// @param wallet The wallet address to query
+    /// @param category `ScoreCategory::tag` of the category, zero for `DEFAULT_CATEGORY`
+    /// @return Same as `get_trust_score_by_category`; reverts with `InvalidCategory` for an unknown tag
+    pub fn get_trust_score_for_category(
+        &self,
+        wallet: Address,
+        category: FixedBytes<32>,
+    ) -> Result<TrustScore, Vec<u8>> {
+        let category = ScoreCategory::from_tag(category).ok_or_else(|| InvalidCategory {}.encode())?;
+        Ok(self.get_trust_score_by_category(wallet, category as u8))
+    }
+
+    /// Check if a wallet is trusted in the category named by a bytes32 tag
+    /// @param wallet The wallet address to check
+    /// @param category `ScoreCategory::tag` of the category, zero to fall back to `is_trusted`
+    /// @return False for an unknown tag; otherwise as `is_trusted_for_category`
+    pub fn is_trusted_in(&self, wallet: Address, category: FixedBytes<32>) -> bool {
+        match ScoreCategory::from_tag(category) {
+            Some(ScoreCategory::Aggregate) => self.is_trusted(wallet),
+            Some(category) => self.is_trusted_for_category(wallet, category as u8),
+            None => false,
+        }
+    }
+
+    /// Check if a wallet's unexpired, decayed score in one category meets the threshold
+    /// @param wallet The wallet address to check
+    /// @param category The score category (see `ScoreCategory`)
+    /// @return False while the wallet is blacklisted, revoked or its score is challenged, and once
+    /// the score is past its signed `validUntil`, whatever its value
+    pub fn is_trusted_for_category(&self, wallet: Address, category: u8) -> bool {
+        let blocked = self.blacklisted.get(wallet) || self.challenged.get(wallet) || self.revoked.get(wallet);
+        let trust_score = self.get_trust_score_by_category(wallet, category);
+        clears_threshold(
+            blocked,
+            &trust_score,
+            self.decayed_score(&trust_score),
+            self.trust_threshold.get(),
+            evm::block_timestamp(),
+        )
+    }
+
+    /// Get the aggregate score a wallet effectively holds
+    /// @param wallet The wallet address to query
+    /// @return The decayed score as used by `is_trusted`, or 0 if the wallet is blacklisted
+    pub fn get_effective_trust(&self, wallet: Address) -> u16 {
+        if self.blacklisted.get(wallet) {
+            return 0;
+        }
+        self.get_effective_score(wallet)
+    }
+
+    /// Get the confidence of a wallet's aggregate score
+    /// @param wallet The wallet address to query
+    /// @return How much data backed the score (0-100), 0 if there is no score
+    pub fn get_confidence(&self, wallet: Address) -> u16 {
+        self.get_trust_score(wallet).confidence
+    }
+
+    /// Check if a wallet is trusted and its aggregate score was computed with enough confidence
+    /// @param wallet The wallet address to check
+    /// @param min_confidence Minimum confidence (0-100) the score must carry
+    pub fn is_trusted_with_confidence(&self, wallet: Address, min_confidence: u16) -> bool {
+        self.is_trusted(wallet) && self.get_confidence(wallet) >= min_confidence
+    }
+
+    /// Get a wallet's aggregate score after linear age-based decay
+    /// @param wallet The wallet address to query
+    /// @return The stored score reduced by `decay_rate_per_day` for its age, floored at `decay_floor`
+    ///
+    /// Computed on read only; the stored score is never rewritten.
+    pub fn get_effective_score(&self, wallet: Address) -> u16 {
+        self.decayed_score(&self.get_trust_score(wallet))
+    }
+
+    /// Get how a wallet's aggregate score moved in its latest update
+    /// @param wallet The wallet address to query
+    /// @return (current score minus previous score, timestamp of the previous score);
+    ///         (0, 0) when there is no previous score
+    pub fn get_score_delta(&self, wallet: Address) -> (i32, u32) {
+        let previous = self.prev_scores.get(wallet);
+        let current = self.read_score(wallet, ScoreCategory::Aggregate as u8);
+        score_delta(&previous, &current)
+    }
+
+    /// Get the URI of a wallet's aggregate score explanation, like ERC-721 `tokenURI`
+    /// @param wallet The wallet address to query
+    /// @return The URI set for the score's `metadataHash`, otherwise the base URI followed
+    ///         by the lowercase hex hash; empty if there is no score
+    pub fn get_metadata_uri(&self, wallet: Address) -> String {
+        let trust_score = self.get_trust_score(wallet);
+        if trust_score.timestamp == 0 {
+            return String::new();
+        }
+
+        metadata_uri(
+            self.metadata_uris.get(trust_score.metadataHash).get_string(),
+            self.metadata_base_uri.get_string(),
+            trust_score.metadataHash,
+        )
+    }
+
+    /// Get the base URI explanation metadata is served from
+    pub fn get_metadata_base_uri(&self) -> String {
+        self.metadata_base_uri.get_string()
+    }
+
+    /// Get the daily decay rate in basis points
+    pub fn get_decay_rate(&self) -> u16 {
+        self.decay_rate_per_day.get()
+    }
+
+    /// Get the score below which decay stops
+    pub fn get_decay_floor(&self) -> u16 {
+        self.decay_floor.get()
+    }
+
+    /// Check whether a wallet has an aggregate score that has not expired
+    pub fn is_score_valid(&self, wallet: Address) -> bool {
+        self.get_trust_score(wallet).timestamp != 0
+    }
+
+    /// Get the seconds elapsed since a wallet's aggregate score was computed
+    /// @param wallet The wallet address to query
+    /// @return Age in seconds, 0 if the wallet has no unexpired score
+    pub fn get_score_age(&self, wallet: Address) -> u32 {
+        let trust_score = self.get_trust_score_by_category(wallet, ScoreCategory::Aggregate as u8);
+        score_age(trust_score.timestamp, evm::block_timestamp())
+    }
+
+    /// Check whether a wallet's aggregate score is older than `update_score` would accept
+    /// @param wallet The wallet address to query
+    /// @return True if the score is absent or past its source's staleness window
+    ///
+    /// A score exactly at the window boundary is still fresh, matching `update_score`.
+    pub fn is_score_stale(&self, wallet: Address) -> bool {
+        let trust_score = self.get_trust_score_by_category(wallet, ScoreCategory::Aggregate as u8);
+        is_absent_or_stale(trust_score.timestamp, evm::block_timestamp(), self.staleness_window(trust_score.source))
+    }
+
+    /// Get everything a trust badge needs in one call
+    /// @param wallet The wallet address to query
+    /// @return (score, timestamp, age_seconds, is_trusted, is_stale), each field equal to
+    ///         what `get_trust_score`, `get_score_age`, `is_trusted` and `is_score_stale` return
+    pub fn get_wallet_status(&self, wallet: Address) -> (u16, u32, u32, bool, bool) {
+        let trust_score = self.get_trust_score(wallet);
+        (
+            trust_score.score,
+            trust_score.timestamp,
+            self.get_score_age(wallet),
+            self.is_trusted(wallet),
+            self.is_score_stale(wallet),
+        )
+    }
+
+    /// Get the maximum accepted score age for sources without their own TTL
+    pub fn get_max_score_age(&self) -> u32 {
+        self.default_staleness_window.get()
+    }
+
+    /// Get the maximum accepted age of a submitted update's timestamp
+    pub fn get_max_timestamp_skew(&self) -> u32 {
+        self.max_timestamp_skew.get()
+    }
+
+    /// Get the score time-to-live in seconds (0 = scores never expire)
+    pub fn get_score_ttl(&self) -> u32 {
+        self.score_ttl_seconds.get()
+    }
+
+    /// Get a wallet's retained score history, oldest first
+    /// @param wallet The wallet address to query
+    /// @return Up to `history_depth` past scores in chronological order
+    pub fn get_score_history(&self, wallet: Address) -> Vec<TrustScore> {
+        let (len, head, start) = self.history_bounds(wallet);
+        (0..len - start)
+            .filter_map(|i| self.score_history.get(wallet).get(history_position(len, head, start, i)))
+            .collect()
+    }
+
+    /// Get a wallet's retained score history, newest first
+    /// @param wallet The wallet address to query
+    /// @return Up to `history_depth` past scores, most recent update at index 0
+    pub fn get_recent_scores(&self, wallet: Address) -> Vec<TrustScore> {
+        let (len, head, start) = self.history_bounds(wallet);
+        (0..len - start)
+            .rev()
+            .filter_map(|i| self.score_history.get(wallet).get(history_position(len, head, start, i)))
+            .collect()
+    }
+
+    /// Get a wallet's time-weighted average aggregate score over the last `window_seconds`
+    /// @param wallet The wallet address to query
+    /// @param window_seconds Length of the averaging window ending at the current block
+    /// @return Average of the retained history entries, each weighted by how long it was in
+    /// force inside the window; the latest stored score if no entry covers any of the window
+    ///
+    /// The entry in force when the window opens counts from the window start, so a
+    /// single fresh update moves the average only in proportion to its age.
+    pub fn get_twa_score(&self, wallet: Address, window_seconds: u32) -> u16 {
+        let history = self.get_score_history(wallet);
+        let now = evm::block_timestamp();
+        time_weighted_average(&history, now.saturating_sub(window_seconds), now).unwrap_or_else(|| {
+            self.read_score(wallet, ScoreCategory::Aggregate as u8)
+                .score
+        })
+    }
+
+    /// Get the direction a wallet's score has been moving over its latest updates
+    /// @param wallet The wallet address to query
+    /// @param window Number of most recent history entries to fit, capped at the retained history
+    /// @return Least-squares slope in score points per update, times 100 (so -250 means the
+    /// score fell 2.5 points per update); 0 with fewer than 2 entries
+    ///
+    /// Entries are spaced one update apart regardless of their timestamps. The slope is
+    /// truncated toward zero and saturates at the `i16` bounds.
+    pub fn get_score_trend(&self, wallet: Address, window: u8) -> i16 {
+        let history = self.get_score_history(wallet);
+        let count = history.len().min(window as usize);
+        if count < 2 {
+            return 0;
+        }
+
+        let n = count as i64;
+        let (mut sum_x, mut sum_y, mut sum_xy, mut sum_xx) = (0i64, 0i64, 0i64, 0i64);
+        for (x, entry) in history[history.len() - count..].iter().enumerate() {
+            let (x, y) = (x as i64, entry.score as i64);
+            sum_x += x;
+            sum_y += y;
+            sum_xy += x * y;
+            sum_xx += x * x;
+        }
+
+        // slope = (nΣxy - ΣxΣy) / (nΣx² - (Σx)²), scaled by 100 before dividing
+        let numerator = n * sum_xy - sum_x * sum_y;
+        let denominator = n * sum_xx - sum_x * sum_x;
+        (numerator * 100 / denominator).clamp(i16::MIN as i64, i16::MAX as i64) as i16
+    }
+
+    /// Get a single history entry
+    /// @param wallet The wallet address to query
+    /// @param index Position in the history, 0 being the oldest retained entry
+    pub fn get_score_at_index(&self, wallet: Address, index: u32) -> Result<TrustScore, Vec<u8>> {
+        let (len, head, start) = self.history_bounds(wallet);
+        if index >= len - start {
+            return Err(HistoryIndexOutOfRange {}.encode());
+        }
+
+        self.score_history
+            .get(wallet)
+            .get(history_position(len, head, start, index))
+            .ok_or_else(|| HistoryIndexOutOfRange {}.encode())
+    }
+
+    /// Get the history entry that was current at a point in time
+    /// @param wallet The wallet address to query
+    /// @param target_ts Unix timestamp to look up
+    /// @return The retained entry with the largest timestamp not after `target_ts`
+    ///
+    /// Entries are ordered by timestamp, so this binary-searches with O(log n) reads.
+    /// Reverts with `InvalidTimestamp` if `target_ts` precedes the oldest retained entry.
+    pub fn get_trust_score_at_timestamp(&self, wallet: Address, target_ts: u32) -> Result<TrustScore, Vec<u8>> {
+        let (len, head, start) = self.history_bounds(wallet);
+        let history = self.score_history.get(wallet);
+        let entry_at = |i: u32| history.get(history_position(len, head, start, i));
+
+        // Find the first logical index whose timestamp is after `target_ts`
+        let (mut low, mut high) = (0u32, len - start);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match entry_at(mid) {
+                Some(entry) if entry.timestamp <= target_ts => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+
+        if low == 0 {
+            return Err(InvalidTimestamp {}.encode());
+        }
+        entry_at(low - 1)
+            .ok_or_else(|| InvalidTimestamp {}.encode())
+    }
+
+    /// Get the number of history entries kept per wallet
+    pub fn get_history_depth(&self) -> u8 {
+        self.history_depth.get()
+    }
+
+    /// Get the upper bound of the score scale
+    pub fn get_max_score(&self) -> u16 {
+        self.max_score.get()
+    }
+
+    /// Get the score precision: 0 = percentage (0-100), 1 = basis points (0-10000)
+    pub fn get_score_precision(&self) -> u8 {
+        u8::from(self.max_score.get() == BASIS_POINT_MAX_SCORE)
+    }
+
+    /// Switch between percentage and basis-point scores (owner only)
+    /// @param precision 0 for 0-100, 1 for 0-10000
+    ///
+    /// Thresholds, the default score, the decay floor, both delta limits and every stored
+    /// score, history entry and leaderboard entry are rescaled with it, so the call costs
+    /// O(`scored_wallets`) writes. Blob-backed `TrustScoreV2` records are not rescaled.
+    /// Only the 100 and 10000 scales can be switched; other `init` scales stay fixed.
+    pub fn set_score_precision(&mut self, precision: u8) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let to = match precision {
+            0 => DEFAULT_MAX_SCORE,
+            1 => BASIS_POINT_MAX_SCORE,
+            _ => return Err(InvalidScorePrecision {}.encode()),
+        };
+        let from = self.max_score.get();
+        if from != DEFAULT_MAX_SCORE && from != BASIS_POINT_MAX_SCORE {
+            return Err(InvalidScorePrecision {}.encode());
+        }
+        if from == to {
+            return Ok(());
+        }
+
+        self.max_score.set(to);
+
+        let threshold = rescale(self.trust_threshold.get(), from, to);
+        self.trust_threshold.set(threshold);
+        let default_score = rescale(self.default_score.get(), from, to);
+        self.default_score.set(default_score);
+        let floor = rescale(self.decay_floor.get(), from, to);
+        self.decay_floor.set(floor);
+        let max_delta = rescale(self.max_score_delta.get(), from, to);
+        self.max_score_delta.set(max_delta);
+        let min_delta = rescale(self.min_score_delta.get(), from, to);
+        self.min_score_delta.set(min_delta);
+
+        for i in 0..self.scored_wallets.len() {
+            if let Some(wallet) = self.scored_wallets.get(i) {
+                self.rescale_wallet(wallet, from, to);
+            }
+        }
+        let leaderboard: Vec<(Address, u16)> = self
+            .leaderboard_entries()
+            .into_iter()
+            .map(|(wallet, score)| (wallet, rescale(score, from, to)))
+            .collect();
+        self.write_leaderboard(&leaderboard);
+
+        evm::log(ScorePrecisionChanged {
+            new_precision: precision,
+        });
+
+        Ok(())
+    }
+
+    /// Get the current trust threshold
+    pub fn get_trust_threshold(&self) -> u16 {
+        self.trust_threshold.get()
+    }
+
+    /// Get the nonce the next update or revocation signature for `wallet` must use
+    /// @param wallet The wallet being scored
+    /// @return The nonce under the current primary oracle, 0 for a fresh wallet
+    ///
+    /// Signers should read this right before signing; every accepted update or
+    /// revocation increments it by one.
+    pub fn get_nonce(&self, wallet: Address) -> U256 {
+        self.oracle_nonces.get(wallet).get(self.oracle_address.get())
+    }
+
+    /// Get the nonce a signature for `wallet` must use under a given primary oracle
+    /// @param wallet The wallet being scored
+    /// @param oracle The primary oracle address the nonce space belongs to
+    pub fn get_nonce_for_oracle(&self, wallet: Address, oracle: Address) -> U256 {
+        self.oracle_nonces.get(wallet).get(oracle)
+    }
+
+    /// Get the share of scored wallets whose aggregate score is below this wallet's
+    /// @param wallet The wallet address to query
+    /// @return Percentile from 0 to 99, 0 if the wallet has no score
+    ///
+    /// Counts every stored score, including ones past their TTL.
+    pub fn get_score_percentile(&self, wallet: Address) -> u8 {
+        let trust_score = self.read_score(wallet, ScoreCategory::Aggregate as u8);
+        let total = self.total_scored_wallets.get() as u64;
+        if trust_score.timestamp == 0 || total == 0 {
+            return 0;
+        }
+
+        let below: u64 = (0..self.histogram_bucket(trust_score.score))
+            .filter_map(|score| self.score_histogram.get(score))
+            .map(u64::from)
+            .sum();
+        (below * 100 / total) as u8
+    }
+
+    /// Get the number of wallets at each aggregate score percentage of `max_score`
+    /// @return 101 counts, index = score as a whole percentage (= score on a 0-100 scale)
+    pub fn get_score_distribution(&self) -> Vec<u32> {
+        (0..=100).filter_map(|score| self.score_histogram.get(score)).collect()
+    }
+
+    /// Get the number of wallets whose aggregate score is trusted
+    ///
+    /// Each wallet's status is evaluated when its score is written and dropped when it
+    /// is revoked, so the count reflects the threshold, decay and flags in force at the
+    /// last write; changing the threshold does not recount existing wallets.
+    pub fn get_trusted_count(&self) -> U256 {
+        self.trusted_count.get()
+    }
+
+    /// Get the number of wallets that have ever received a score
+    ///
+    /// The list is append-only: revoked and frozen wallets stay listed.
+    pub fn get_scored_wallet_count(&self) -> u64 {
+        self.scored_wallets.len() as u64
+    }
+
+    /// Get a wallet from the scored-wallet list, for paginated enumeration
+    /// @param index Position in first-scored order, below `get_scored_wallet_count`
+    pub fn get_scored_wallet_at_index(&self, index: u64) -> Result<Address, Vec<u8>> {
+        self.scored_wallets
+            .get(index as usize)
+            .ok_or_else(|| IndexOutOfRange {}.encode())
+    }
+
+    /// Get wallets whose stored aggregate score lies in `[min_score, max_score]`
+    /// @param max_return Maximum number of wallets returned, capped at `MAX_BATCH_READ`
+    /// @return Matching wallets in first-scored order
+    ///
+    /// Scans `scored_wallets` linearly and stops once the histogram says no further
+    /// match can exist. Fine for small and medium registries; large deployments should
+    /// index `ScoreUpdated` off-chain instead. Like the histogram, ignores TTL and decay.
+    pub fn get_trust_scores_in_range(&self, min_score: u16, max_score: u16, max_return: u16) -> Vec<Address> {
+        let mut matches = Vec::new();
+        if min_score > max_score || max_return == 0 {
+            return matches;
+        }
+
+        // Every match sits in one of these buckets, so their total bounds the scan
+        let buckets = self.histogram_bucket(min_score)..=self.histogram_bucket(max_score);
+        let candidates: u64 = buckets
+            .clone()
+            .filter_map(|bucket| self.score_histogram.get(bucket))
+            .map(u64::from)
+            .sum();
+        let limit = (max_return as usize).min(MAX_BATCH_READ);
+
+        let mut seen = 0u64;
+        for i in 0..self.scored_wallets.len() {
+            if matches.len() >= limit || seen >= candidates {
+                break;
+            }
+            let Some(wallet) = self.scored_wallets.get(i) else {
+                continue;
+            };
+
+            let trust_score = self.read_score(wallet, ScoreCategory::Aggregate as u8);
+            if trust_score.timestamp == 0 {
+                continue;
+            }
+
+            if buckets.contains(&self.histogram_bucket(trust_score.score)) {
+                seen += 1;
+            }
+            if (min_score..=max_score).contains(&trust_score.score) {
+                matches.push(wallet);
+            }
+        }
+
+        matches
+    }
+
+    /// Get the number of wallets with a stored aggregate score
+    pub fn get_total_scored_wallets(&self) -> u32 {
+        self.total_scored_wallets.get()
+    }
+
+    /// Get the highest-scoring wallets
+    /// @return Up to `max_leaderboard_size` (wallet, aggregate score) pairs, best first
+    pub fn get_leaderboard(&self) -> Vec<(Address, u16)> {
+        self.leaderboard_entries()
+    }
+
+    /// Get the number of wallets kept in the leaderboard
+    pub fn get_max_leaderboard_size(&self) -> u16 {
+        self.max_leaderboard_size.get()
+    }
+
+    /// Check whether `init` has run
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.get()
+    }
+
+    /// Chainlink `AggregatorV3Interface.decimals`: scores are whole numbers
+    pub fn decimals(&self) -> u8 {
+        0
+    }
+
+    /// Chainlink `AggregatorV3Interface.description`
+    pub fn description(&self) -> String {
+        String::from("TrustGrid wallet trust score")
+    }
+
+    /// Chainlink-style `latestRoundData` for one wallet's aggregate score
+    /// @param wallet The wallet address to query
+    /// @return (roundId, answer, startedAt, updatedAt, answeredInRound)
+    ///
+    /// `answer` is the wallet's own score (delegations are not followed, matching the
+    /// history behind `get_round_data`) and `updatedAt` its timestamp. Score timestamps
+    /// strictly increase per wallet, so the timestamp doubles as the round id.
+    pub fn latest_round_data(&self, wallet: Address) -> (U80, I256, U256, U256, U80) {
+        round_data(&self.get_trust_score_by_category(wallet, ScoreCategory::Aggregate as u8))
+    }
+
+    /// Chainlink-style `getRoundData` for one wallet's aggregate score history
+    /// @param wallet The wallet address to query
+    /// @param round_id A round id returned earlier, i.e. a score timestamp
+    /// @return The round that was current at `round_id`, shaped like `latest_round_data`
+    pub fn get_round_data(&self, wallet: Address, round_id: U80) -> Result<(U80, I256, U256, U256, U80), Vec<u8>> {
+        let target_ts = u32::try_from(round_id).map_err(|_| InvalidTimestamp {}.encode())?;
+        let trust_score = self.get_trust_score_at_timestamp(wallet, target_ts)?;
+        Ok(round_data(&trust_score))
+    }
+
+    /// Get the storage layout version, so proxies can check upgrade compatibility
+    pub fn get_storage_version(&self) -> u32 {
+        STORAGE_VERSION
+    }
+
+    /// Get the EIP-155 chain id that signatures are bound to
+    pub fn get_chain_id(&self) -> U256 {
+        U256::from(block::chainid())
+    }
+
+    /// Get the EIP-712 domain separator so backends can reconstruct the signed digest
+    pub fn get_domain_separator(&self) -> FixedBytes<32> {
+        self.current_domain_separator()
+    }
+
+    /// Get the EIP-712 domain separator (original selector, same value as `get_domain_separator`)
+    pub fn domain_separator(&self) -> FixedBytes<32> {
+        self.current_domain_separator()
+    }
+
+    /// EIP-5267 description of the signing domain
+    /// @return (fields, name, version, chainId, verifyingContract, salt, extensions)
+    ///
+    /// Lets signers confirm which chain and which deployment a signature is scoped to.
+    pub fn eip712_domain(
+        &self,
+    ) -> (FixedBytes<1>, String, String, U256, Address, FixedBytes<32>, Vec<U256>) {
+        (
+            // name, version, chainId and verifyingContract are set
+            FixedBytes([0x0f]),
+            String::from_utf8_lossy(DOMAIN_NAME).into_owned(),
+            String::from_utf8_lossy(DOMAIN_VERSION).into_owned(),
+            U256::from(block::chainid()),
+            contract::address(),
+            FixedBytes::ZERO,
+            Vec::new(),
+        )
+    }
+
+    /// Propose a new oracle that can be activated once `ORACLE_TIMELOCK_SECONDS` pass (`ADMIN_ROLE` only)
+    /// @param new_oracle The new oracle address
+    ///
+    /// The delay gives operators time to react if the owner key is compromised.
+    /// A new proposal replaces any pending rotation and restarts the clock; proposed
+    /// oracle additions are tracked separately and stay pending.
+    pub fn propose_oracle(&mut self, new_oracle: Address) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        if new_oracle == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        let activation_time = block::timestamp() + ORACLE_TIMELOCK_SECONDS;
+        self.pending_oracle.set(new_oracle);
+        self.oracle_activation_time.set(activation_time);
+
+        evm::log(OracleProposed {
+            proposed: new_oracle,
+            activation_time,
+        });
+
+        Ok(())
+    }
+
+    /// Apply the pending oracle rotation once its time lock expired (anyone)
+    ///
+    /// The proposal from `propose_oracle` replaces the whole oracle set and resets the
+    /// quorum to that single oracle. The replaced oracle's score update signatures keep
+    /// verifying for `ORACLE_ROTATION_GRACE_SECONDS`, so updates queued before the switch
+    /// do not revert. Oracles proposed by `add_oracle`, `register_oracle` or `grant_role`
+    /// are applied by `activate_oracle_weight` instead.
+    pub fn activate_oracle(&mut self) -> Result<(), Vec<u8>> {
+        let new_oracle = self.pending_oracle.get();
+        check_oracle_activation(new_oracle, self.oracle_activation_time.get(), block::timestamp())?;
+
+        // Checked up front too: a re-activated oracle already at its weight skips the weight check
+        self.require_stake(new_oracle)?;
+
+        self.pending_oracle.set(Address::ZERO);
+        self.oracle_activation_time.set(0);
+
+        let old_oracle = self.oracle_address.get();
+        self.oracle_address.set(new_oracle);
+
+        // Updates the old key signed before the switch can still land for a while
+        let expiry = block::timestamp() + ORACLE_ROTATION_GRACE_SECONDS;
+        self.previous_oracle.set(old_oracle);
+        self.rotation_expiry.set(expiry);
+        evm::log(OracleRotationScheduled {
+            previousOracle: old_oracle,
+            newOracle: new_oracle,
+            expiry,
+        });
+
+        // Collapse the authorized set to just the new oracle, which must be able to
+        // meet the quorum alone
+        while let Some(oracle) = self.oracle_list.get(0) {
+            self.set_oracle_weight(oracle, 0)?;
+        }
+        self.set_oracle_weight(new_oracle, 1)?;
+        self.oracle_quorum.set(1);
+        self.quorum_threshold.set(0);
+
+        evm::log(OracleUpdated {
+            oldOracle: old_oracle,
+            newOracle: new_oracle,
+        });
+
+        Ok(())
+    }
+
+    /// Discard the pending oracle rotation (`ADMIN_ROLE` only)
+    pub fn cancel_oracle_proposal(&mut self) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        let proposed = self.pending_oracle.get();
+        if proposed == Address::ZERO {
+            return Err(NoPendingOracle {}.encode());
+        }
+
+        self.pending_oracle.set(Address::ZERO);
+        self.oracle_activation_time.set(0);
+
+        evm::log(OracleProposalCancelled { proposed });
+
+        Ok(())
+    }
+
+    /// Get the pending oracle rotation and the time it can be activated (zeroes if none)
+    pub fn get_pending_oracle(&self) -> (Address, u64) {
+        (self.pending_oracle.get(), self.oracle_activation_time.get())
+    }
+
+    /// Apply an oracle's proposed weight once its time lock expired (anyone)
+    /// @param oracle An oracle proposed by `add_oracle`, `register_oracle` or `grant_role`
+    ///
+    /// Each oracle's proposal is tracked on its own, so several can be pending at once
+    /// and activating one leaves the rest of the set in place.
+    pub fn activate_oracle_weight(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        let activation_at = self.oracle_activation_at.get(oracle);
+        if activation_at == 0 {
+            return Err(NoPendingOracle {}.encode());
+        }
+        check_oracle_activation(oracle, activation_at, block::timestamp())?;
+
+        let weight = self.pending_oracle_weights.get(oracle);
+        self.oracle_activation_at.setter(oracle).set(0);
+        self.pending_oracle_weights.setter(oracle).set(0);
+
+        self.set_oracle_weight(oracle, weight)
+    }
+
+    /// Discard an oracle's proposed weight (`ADMIN_ROLE` only)
+    pub fn cancel_oracle_weight_proposal(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        if self.oracle_activation_at.get(oracle) == 0 {
+            return Err(NoPendingOracle {}.encode());
+        }
+
+        self.oracle_activation_at.setter(oracle).set(0);
+        self.pending_oracle_weights.setter(oracle).set(0);
+
+        evm::log(OracleProposalCancelled { proposed: oracle });
+
+        Ok(())
+    }
+
+    /// Get an oracle's proposed weight and the time it can be activated (zeroes if none)
+    pub fn get_pending_oracle_weight(&self, oracle: Address) -> (u16, u64) {
+        (self.pending_oracle_weights.get(oracle), self.oracle_activation_at.get(oracle))
+    }
+
+    /// Stake ETH towards becoming (or remaining) an oracle (anyone)
+    ///
+    /// Stake accumulates across calls; `activate_oracle` and `activate_oracle_weight` require the proposed oracle
+    /// to hold at least `oracle_staking_requirement`.
+    #[payable]
+    pub fn stake_to_become_oracle(&mut self) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        let amount = msg::value();
+        let staked = self.staked_amounts.get(sender) + amount;
+        self.staked_amounts.setter(sender).set(staked);
+        self.total_staked.set(self.total_staked.get() + amount);
+
+        evm::log(Staked {
+            oracle: sender,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the caller's whole stake (former oracles after `STAKE_COOLDOWN_SECONDS`)
+    ///
+    /// Active and pending oracles cannot withdraw; an oracle removed from the set
+    /// must wait out the cooldown so late misbehaviour can still be slashed.
+    pub fn withdraw_stake(&mut self) -> Result<(), Vec<u8>> {
+        self.lock()?;
+
+        let sender = msg::sender();
+        let amount = self.staked_amounts.get(sender);
+        if amount == U256::ZERO {
+            return Err(NoStake {}.encode());
+        }
+
+        if self.has_role(ORACLE_ROLE, sender)
+            || sender == self.pending_oracle.get()
+            || self.oracle_activation_at.get(sender) != 0
+            || block::timestamp() < self.stake_unlock_time.get(sender)
+        {
+            return Err(StakeLocked {}.encode());
+        }
+
+        // Clear the stake before sending so a reentrant call finds nothing to withdraw
+        self.staked_amounts.setter(sender).set(U256::ZERO);
+        self.total_staked.set(self.total_staked.get() - amount);
+        transfer_eth(sender, amount)?;
+
+        evm::log(StakeWithdrawn {
+            oracle: sender,
+            amount,
+        });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Reduce an oracle's stake as a penalty (owner only)
+    /// @param oracle The penalized oracle
+    /// @param amount Wei to slash, capped at the current stake
+    ///
+    /// Slashed ETH stays in the contract until the owner recovers it with `withdraw`.
+    pub fn slash_oracle(&mut self, oracle: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let staked = self.staked_amounts.get(oracle);
+        let slashed = amount.min(staked);
+        self.staked_amounts.setter(oracle).set(staked - slashed);
+        self.total_staked.set(self.total_staked.get() - slashed);
+
+        evm::log(StakeSlashed {
+            oracle,
+            amount: slashed,
+        });
+
+        Ok(())
+    }
+
+    /// Set the stake an oracle must hold to be granted or raised in weight (owner only)
+    /// @param amount Minimum stake in wei, 0 disables the requirement
+    pub fn set_oracle_staking_requirement(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.oracle_staking_requirement.set(amount);
+
+        evm::log(StakingRequirementUpdated { amount });
+
+        Ok(())
+    }
+
+    /// Get the stake an oracle must hold to be granted or raised in weight
+    pub fn get_oracle_staking_requirement(&self) -> U256 {
+        self.oracle_staking_requirement.get()
+    }
+
+    /// Get an account's stake and the earliest time it may be withdrawn
+    pub fn get_stake(&self, oracle: Address) -> (U256, u64) {
+        (self.staked_amounts.get(oracle), self.stake_unlock_time.get(oracle))
+    }
+
+    /// Send every wei not backing a stake to `to` (owner only)
+    /// @param to Recipient of the recovered ETH
+    ///
+    /// Recovers collected update fees, slashed stake and ETH forced in without a call
+    /// (self-destruct, block rewards); oracle stakes stay withdrawable by their owners.
+    pub fn withdraw(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.lock()?;
+
+        let amount = withdrawable(contract::balance(), self.total_staked.get());
+        self.collected_fees.set(U256::ZERO);
+        transfer_eth(to, amount)?;
+
+        evm::log(Withdrawal { to, amount });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Set the fee a relayer must attach per score update (owner only)
+    /// @param fee Wei per update, 0 makes updates free
+    pub fn set_update_fee(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.update_fee.set(fee);
+
+        evm::log(UpdateFeeUpdated { fee });
+
+        Ok(())
+    }
+
+    /// Get the fee a relayer must attach per score update
+    pub fn get_update_fee(&self) -> U256 {
+        self.update_fee.get()
+    }
+
+    /// Get the update fees paid in since the last `withdraw`
+    pub fn get_collected_fees(&self) -> U256 {
+        self.collected_fees.get()
+    }
+
+    /// Get the oracle retired by the last rotation and when its grace period ends
+    pub fn get_previous_oracle(&self) -> (Address, u64) {
+        (self.previous_oracle.get(), self.rotation_expiry.get())
+    }
+
+    /// Update trust threshold (`ADMIN_ROLE` only)
+    /// @param new_threshold The new trust threshold
+    pub fn update_trust_threshold(&mut self, new_threshold: u16) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        if new_threshold > self.max_score.get() {
+            return Err(InvalidThreshold {}.encode());
+        }
+
+        let old_threshold = self.trust_threshold.get();
+        self.trust_threshold.set(new_threshold);
+
+        evm::log(ThresholdUpdated {
+            oldThreshold: old_threshold,
+            newThreshold: new_threshold,
+            by: msg::sender(),
+        });
+
+        Ok(())
+    }
+
+    /// Set the aggregate score served for never-scored wallets (owner only)
+    /// @param default_score Baseline score, at most `max_score`; 0 restores the old behaviour
+    pub fn set_default_score(&mut self, default_score: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if default_score > self.max_score.get() {
+            return Err(InvalidScore {}.encode());
+        }
+
+        self.default_score.set(default_score);
+
+        evm::log(DefaultScoreUpdated { default_score });
+
+        Ok(())
+    }
+
+    /// Get the aggregate score served for never-scored wallets
+    pub fn get_default_score(&self) -> u16 {
+        self.default_score.get()
+    }
+
+    /// Halt all score mutations (`PAUSER_ROLE` or guardian); reads keep working
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        check_pauser(sender, self.has_role(PAUSER_ROLE, sender), self.guardian.get())?;
+
+        self.paused.set(true);
+        evm::log(Paused { by: msg::sender() });
+        Ok(())
+    }
+
+    /// Resume score mutations (`PAUSER_ROLE` only)
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.only_role(PAUSER_ROLE)?;
+
+        self.paused.set(false);
+        evm::log(Unpaused { by: msg::sender() });
+        Ok(())
+    }
+
+    /// Set the maximum accepted score age for one source (authorized oracles only)
+    /// @param source The source identifier
+    /// @param max_age Maximum age in seconds, 0 reverts to the default window
+    pub fn set_source_ttl(&mut self, source: FixedBytes<32>, max_age: u32) -> Result<(), Vec<u8>> {
+        if !self.has_role(ORACLE_ROLE, msg::sender()) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        self.source_ttl.setter(source).set(max_age);
+
+        evm::log(SourceTTLUpdated {
+            source,
+            new_ttl: max_age,
+        });
+
+        Ok(())
+    }
+
+    /// Set a subcategory's weight in `compute_composite` (owner only)
+    /// @param category A non-aggregate `ScoreCategory`
+    /// @param weight_bps Weight in basis points, at most 10000; 0 excludes the category
+    pub fn set_category_weight(&mut self, category: u8, weight_bps: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        match ScoreCategory::from_u8(category) {
+            None | Some(ScoreCategory::Aggregate) => return Err(InvalidCategory {}.encode()),
+            Some(_) => {}
+        }
+
+        if weight_bps > 10_000 {
+            return Err(InvalidCategoryWeight {}.encode());
+        }
+
+        self.category_weights.setter(category).set(weight_bps);
+
+        evm::log(CategoryWeightUpdated {
+            category,
+            weight_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Get a subcategory's weight in `compute_composite`, in basis points
+    pub fn get_category_weight(&self, category: u8) -> u16 {
+        self.category_weights.get(category)
+    }
+
+    /// Set a source's weight in `get_aggregate_score` (owner only)
+    /// @param source The source identifier
+    /// @param weight Weight in basis points, at most 10000; 0 excludes the source
+    ///
+    /// The aggregate divides by the weights of the sources a wallet actually has, so
+    /// weights need not sum to 10000.
+    pub fn set_source_weight(&mut self, source: FixedBytes<32>, weight: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if weight > 10_000 {
+            return Err(InvalidSourceWeight {}.encode());
+        }
+
+        self.source_weights.setter(source).set(weight);
+
+        evm::log(SourceWeightUpdated { source, weight });
+
+        Ok(())
+    }
+
+    /// Get a source's weight in `get_aggregate_score`, in basis points
+    pub fn get_source_weight(&self, source: FixedBytes<32>) -> u16 {
+        self.source_weights.get(source)
+    }
+
+    /// Set the maximum accepted score age for sources without their own TTL (owner only)
+    /// @param max_age Maximum age in seconds, between one minute and one week
+    pub fn set_max_score_age(&mut self, max_age: u32) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.set_default_staleness_window(max_age)
+    }
+
+    /// Set how old a submitted update's timestamp may be, e.g. 300 for high-frequency feeds (owner only)
+    /// @param seconds Maximum age in seconds, between one minute and one week
+    ///
+    /// An update must also be fresh under its source's staleness window, so a slow feed
+    /// needs both this and its TTL raised.
+    pub fn set_max_timestamp_skew(&mut self, seconds: u32) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if !(MIN_TIMESTAMP_SKEW..=MAX_TIMESTAMP_SKEW).contains(&seconds) {
+            return Err(InvalidTimestampSkew {}.encode());
+        }
+
+        self.max_timestamp_skew.set(seconds);
+
+        evm::log(TimestampSkewUpdated { new_skew: seconds });
+
+        Ok(())
+    }
+
+    /// Link an explanation metadata hash to the URI it is stored at (authorized oracles only)
+    /// @param metadata_hash The `metadataHash` carried by scores
+    /// @param uri Full location of the explanation, e.g. "ipfs://<cid>"
+    pub fn set_metadata_uri(&mut self, metadata_hash: FixedBytes<32>, uri: String) -> Result<(), Vec<u8>> {
+        if !self.has_role(ORACLE_ROLE, msg::sender()) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        self.metadata_uris.setter(metadata_hash).set_str(&uri);
+
+        evm::log(MetadataUriSet {
+            metadataHash: metadata_hash,
+            uri,
+        });
+
+        Ok(())
+    }
+
+    /// Attach a human-readable label to a source identifier (authorized oracles only)
+    /// @param source_id The `bytes32` identifier used in score updates
+    /// @param label Display name, e.g. "chainlink-v3"
+    pub fn register_source(&mut self, source_id: FixedBytes<32>, label: String) -> Result<(), Vec<u8>> {
+        if !self.has_role(ORACLE_ROLE, msg::sender()) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        self.source_labels.setter(source_id).set_str(&label);
+
+        evm::log(SourceRegistered {
+            source: source_id,
+            label,
+        });
+
+        Ok(())
+    }
+
+    /// Get the label registered for a source identifier (empty if none)
+    pub fn get_source_label(&self, source_id: FixedBytes<32>) -> String {
+        self.source_labels.get(source_id).get_string()
+    }
+
+    /// Get the maximum accepted score age for a source, falling back to the default window
+    pub fn get_source_ttl(&self, source: FixedBytes<32>) -> u32 {
+        self.staleness_window(source)
+    }
+
+    /// Set the base URI explanation metadata is served from (owner only)
+    /// @param uri Prefix the hex `metadataHash` is appended to, e.g. "ipfs://<cid>/"
+    pub fn set_metadata_base_uri(&mut self, uri: String) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.metadata_base_uri.set_str(&uri);
+
+        evm::log(MetadataBaseURIUpdated { uri });
+
+        Ok(())
+    }
+
+    /// Set the ERC-721 contract that mints a score attestation after each update (owner only)
+    /// @param nft_contract Contract exposing `mint(address,uint256,uint16,uint32)`, zero disables minting
+    pub fn set_score_nft_contract(&mut self, nft_contract: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.score_nft_contract.set(nft_contract);
+
+        evm::log(ScoreNftContractUpdated { nft_contract });
+
+        Ok(())
+    }
+
+    /// Get the ERC-721 attestation contract (zero if disabled)
+    pub fn get_score_nft_contract(&self) -> Address {
+        self.score_nft_contract.get()
+    }
+
+    /// Copy every score write and revocation to a hot-standby oracle (owner only)
+    /// @param mirror The standby, which must list this contract as its `mirror_primary`
+    pub fn set_mirror_oracle(&mut self, mirror: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if mirror == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        self.mirror_oracle.set(mirror);
+        evm::log(MirrorOracleUpdated { mirror });
+        Ok(())
+    }
+
+    /// Stop copying score updates to the standby (owner only)
+    pub fn clear_mirror_oracle(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.mirror_oracle.set(Address::ZERO);
+        evm::log(MirrorOracleUpdated {
+            mirror: Address::ZERO,
+        });
+        Ok(())
+    }
+
+    /// Get the standby oracle score updates are copied to (zero if none)
+    pub fn get_mirror_oracle(&self) -> Address {
+        self.mirror_oracle.get()
+    }
+
+    /// Accept mirrored score updates from a primary oracle (owner only, on the standby)
+    /// @param primary The primary contract, zero stops accepting copies
+    pub fn set_mirror_primary(&mut self, primary: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.mirror_primary.set(primary);
+        evm::log(MirrorPrimaryUpdated { primary });
+        Ok(())
+    }
+
+    /// Get the primary oracle allowed to push mirrored updates (zero if none)
+    pub fn get_mirror_primary(&self) -> Address {
+        self.mirror_primary.get()
+    }
+
+    /// Store a score copied from the primary oracle (`mirror_primary` only)
+    ///
+    /// The primary already verified the oracle signature, which is bound to its own
+    /// address and so could not be re-verified here. The copy must still fit this
+    /// contract's scale, respect its pause and freeze, and be newer than the stored score.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mirror_score(
+        &mut self,
+        wallet: Address,
+        category: u8,
+        score: u16,
+        confidence: u16,
+        timestamp: u32,
+        valid_until: u32,
+        source: FixedBytes<32>,
+        metadata_hash: FixedBytes<32>,
+    ) -> Result<(), Vec<u8>> {
+        self.lock()?;
+        self.only_mirror_primary()?;
+        self.when_not_paused()?;
+
+        if ScoreCategory::from_u8(category).is_none() {
+            return Err(InvalidCategory {}.encode());
+        }
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        let stored = self.read_score(wallet, category);
+        check_mirrored_score(&stored, score, confidence, timestamp, self.max_score.get())?;
+
+        let trust_score = TrustScore {
+            score,
+            confidence,
+            timestamp,
+            validUntil: valid_until,
+            source,
+            metadataHash: metadata_hash,
+        };
+        self.store_score(wallet, category, trust_score)?;
+
+        evm::log(ScoreUpdated {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadataHash: metadata_hash,
+            deadline: 0,
+            validUntil: valid_until,
+        });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Revoke a wallet's scores as the primary oracle did (`mirror_primary` only)
+    /// @param wallet The revoked wallet
+    /// @param reason_hash The primary's revocation reason, zero for a signed revocation
+    pub fn mirror_revocation(&mut self, wallet: Address, reason_hash: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        self.lock()?;
+        self.only_mirror_primary()?;
+        self.when_not_paused()?;
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        self.clear_scores(wallet);
+
+        evm::log(ScoreRevoked {
+            wallet,
+            oracle: msg::sender(),
+            reason_hash,
+            revoked_at: evm::block_timestamp(),
+        });
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Grant a role to an account (`ADMIN_ROLE` only)
+    /// @param role One of `ORACLE_ROLE`, `ADMIN_ROLE` or `PAUSER_ROLE`
+    /// @param account The account receiving the role
+    ///
+    /// Granting `ORACLE_ROLE` proposes the account as an oracle with a weight of 1,
+    /// which takes effect through `activate_oracle_weight` after the time lock.
+    pub fn grant_role(&mut self, role: FixedBytes<32>, account: Address) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        if account == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        if role == ORACLE_ROLE {
+            if self.oracle_registry.get(account) == 0 {
+                self.propose_oracle_weight(account, 1);
+            }
+        } else {
+            self.set_role(role, account, true);
+        }
+
+        Ok(())
+    }
+
+    /// Revoke a role from an account (`ADMIN_ROLE` only)
+    /// @param role One of `ORACLE_ROLE`, `ADMIN_ROLE` or `PAUSER_ROLE`
+    /// @param account The account losing the role
+    ///
+    /// Revoking `ORACLE_ROLE` deregisters the oracle and its weight.
+    pub fn revoke_role(&mut self, role: FixedBytes<32>, account: Address) -> Result<(), Vec<u8>> {
+        self.only_role(ADMIN_ROLE)?;
+
+        if role == ORACLE_ROLE {
+            self.set_oracle_weight(account, 0)?;
+        } else {
+            self.set_role(role, account, false);
+        }
+
+        Ok(())
+    }
+
+    /// Check whether an account holds a role
+    pub fn has_role(&self, role: FixedBytes<32>, account: Address) -> bool {
+        self.roles.get(role).get(account)
+    }
+
+    /// Set the guardian allowed to pause the contract (owner only)
+    /// @param new_guardian The guardian address, zero to remove the role
+    pub fn set_guardian(&mut self, new_guardian: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let old_guardian = self.guardian.get();
+        self.guardian.set(new_guardian);
+
+        evm::log(GuardianUpdated {
+            oldGuardian: old_guardian,
+            newGuardian: new_guardian,
+        });
+
+        Ok(())
+    }
+
+    /// Get the guardian address (zero if none)
+    pub fn get_guardian(&self) -> Address {
+        self.guardian.get()
+    }
+
+    /// Check whether score mutations are currently halted
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Lock a wallet's scores at their current values (owner only); reads are unaffected
+    /// @param wallet The wallet to freeze
+    pub fn freeze_wallet(&mut self, wallet: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.frozen.setter(wallet).set(true);
+        evm::log(WalletFrozen {
+            wallet,
+            by: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Allow oracle updates to a frozen wallet again (owner only)
+    /// @param wallet The wallet to unfreeze
+    pub fn unfreeze_wallet(&mut self, wallet: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.frozen.setter(wallet).set(false);
+        evm::log(WalletUnfrozen {
+            wallet,
+            by: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Register a relayer that submits updates on the oracle's behalf (owner only)
+    /// @param relayer The gas-paying submitter
+    ///
+    /// Relayers never need the signing key: every update is still verified against
+    /// the primary oracle's signature, whose digest binds `oracle_address` and its nonce.
+    pub fn add_relayer(&mut self, relayer: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if relayer == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        self.trusted_relayers.setter(relayer).set(true);
+        evm::log(RelayerAdded {
+            relayer,
+            by: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Deregister a relayer (owner only)
+    /// @param relayer The submitter to remove
+    pub fn remove_relayer(&mut self, relayer: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.trusted_relayers.setter(relayer).set(false);
+        evm::log(RelayerRemoved {
+            relayer,
+            by: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Check whether an address is a registered relayer
+    pub fn is_relayer(&self, relayer: Address) -> bool {
+        self.trusted_relayers.get(relayer)
+    }
+
+    /// Restrict score updates to registered relayers (owner only)
+    /// @param required True to reject updates from any other caller
+    ///
+    /// Off by default. Signatures are verified either way; the allowlist only limits
+    /// who may submit them.
+    pub fn set_require_allowlisted_relayer(&mut self, required: bool) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.require_allowlisted_relayer.set(required);
+        evm::log(RelayerAllowlistToggled { required });
+        Ok(())
+    }
+
+    /// Check whether score updates are restricted to registered relayers
+    pub fn is_relayer_allowlist_required(&self) -> bool {
+        self.require_allowlisted_relayer.get()
+    }
+
+    /// Allow an address to challenge scores (owner only)
+    /// @param challenger The whistleblower or monitor address
+    pub fn add_challenger(&mut self, challenger: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if challenger == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        self.challengers.setter(challenger).set(true);
+        evm::log(ChallengerAdded { challenger });
+        Ok(())
+    }
+
+    /// Withdraw an address's permission to challenge scores (owner only)
+    /// @param challenger The challenger to remove
+    pub fn remove_challenger(&mut self, challenger: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.challengers.setter(challenger).set(false);
+        evm::log(ChallengerRemoved { challenger });
+        Ok(())
+    }
+
+    /// Flag a wallet's score as disputed without changing it (challengers only)
+    /// @param wallet The wallet whose score is disputed
+    /// @param evidence_hash Hash of the off-chain evidence backing the challenge
+    pub fn challenge_score(&mut self, wallet: Address, evidence_hash: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        let challenger = msg::sender();
+        if !self.challengers.get(challenger) {
+            return Err(NotChallenger {}.encode());
+        }
+
+        self.challenged.setter(wallet).set(true);
+
+        evm::log(ScoreChallenged {
+            wallet,
+            challenger,
+            evidence_hash,
+            timestamp: evm::block_timestamp(),
+        });
+
+        Ok(())
+    }
+
+    /// Clear a wallet's challenge flag (authorized oracles only)
+    /// @param wallet The wallet whose challenge is resolved
+    pub fn resolve_challenge(&mut self, wallet: Address) -> Result<(), Vec<u8>> {
+        let oracle = msg::sender();
+        if !self.has_role(ORACLE_ROLE, oracle) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        self.challenged.setter(wallet).set(false);
+        evm::log(ChallengeResolved { wallet, oracle });
+        Ok(())
+    }
+
+    /// File an appeal against the caller's own score (anyone)
+    /// @param appeal_hash Hash of the off-chain appeal document
+    ///
+    /// Filing is only a signal: the score and `is_trusted` stay as they are until an
+    /// oracle resolves the appeal. One appeal may be open per wallet at a time.
+    pub fn file_appeal(&mut self, appeal_hash: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        let wallet = msg::sender();
+        if self.is_appeal_open(wallet) {
+            return Err(AppealPending {}.encode());
+        }
+
+        let filed_at = block::timestamp();
+        self.appeal_hashes.setter(wallet).set(appeal_hash);
+        self.appeal_filed_at.setter(wallet).set(filed_at);
+
+        evm::log(AppealFiled {
+            wallet,
+            appeal_hash,
+            filed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Close a wallet's open appeal, rewriting its score if accepted (authorized oracles only)
+    /// @param wallet The appealing wallet
+    /// @param accepted True to replace the aggregate score with `new_score`
+    /// @param new_score Corrected score (0 to `max_score`), ignored when rejected
+    /// @param confidence Confidence of the corrected score (0-100), ignored when rejected
+    /// @param metadata_hash Hash of the explanation for the correction
+    ///
+    /// An accepted score is stored under `APPEAL_SOURCE` at the current block time. It
+    /// passes the same checks as a signed update, except that it may exceed `max_score_delta`.
+    pub fn resolve_appeal(
+        &mut self,
+        wallet: Address,
+        accepted: bool,
+        new_score: u16,
+        confidence: u16,
+        metadata_hash: FixedBytes<32>,
+    ) -> Result<(), Vec<u8>> {
+        self.lock()?;
+
+        if !self.has_role(ORACLE_ROLE, msg::sender()) {
+            return Err(UnauthorizedOracle {}.encode());
+        }
+
+        if !self.is_appeal_open(wallet) {
+            return Err(NoOpenAppeal {}.encode());
+        }
+
+        if accepted {
+            self.when_not_paused()?;
+
+            let update = appeal_update(wallet, new_score, confidence, metadata_hash, evm::block_timestamp());
+            self.validate_score_update(&update)?;
+
+            let trust_score = TrustScore {
+                score: new_score,
+                confidence,
+                timestamp: update.timestamp,
+                validUntil: 0,
+                source: APPEAL_SOURCE,
+                metadataHash: metadata_hash,
+            };
+            self.store_score(wallet, ScoreCategory::Aggregate as u8, trust_score)?;
+        }
+
+        self.appeal_hashes.setter(wallet).set(FixedBytes::ZERO);
+        self.appeal_filed_at.setter(wallet).set(0);
+
+        evm::log(AppealResolved {
+            wallet,
+            accepted,
+            new_score: if accepted { new_score } else { 0 },
+        });
+
+        if accepted {
+            self.forward_to_mirror(wallet, ScoreCategory::Aggregate as u8);
+        }
+
+        self.unlock();
+        Ok(())
+    }
+
+    /// Get a wallet's open appeal
+    /// @return The appeal hash and filing time, zeroes if none is open or it lapsed
+    pub fn get_appeal(&self, wallet: Address) -> (FixedBytes<32>, u64) {
+        if !self.is_appeal_open(wallet) {
+            return (FixedBytes::ZERO, 0);
+        }
+        (self.appeal_hashes.get(wallet), self.appeal_filed_at.get(wallet))
+    }
+
+    /// Set how long an appeal stays open before it lapses (owner only)
+    /// @param window_seconds Window in seconds, 0 keeps appeals open until resolved
+    pub fn set_appeal_window(&mut self, window_seconds: u32) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.appeal_window_seconds.set(window_seconds);
+
+        evm::log(AppealWindowUpdated { window_seconds });
+
+        Ok(())
+    }
+
+    /// Get how long an appeal stays open before it lapses (0 = until resolved)
+    pub fn get_appeal_window(&self) -> u32 {
+        self.appeal_window_seconds.get()
+    }
+
+    /// Check whether a wallet's score is currently disputed
+    pub fn is_challenged(&self, wallet: Address) -> bool {
+        self.challenged.get(wallet)
+    }
+
+    /// Check whether an address may challenge scores
+    pub fn is_challenger(&self, challenger: Address) -> bool {
+        self.challengers.get(challenger)
+    }
+
+    /// Add or remove a wallet from the blacklist (owner only)
+    /// @param wallet The wallet to update
+    /// @param blacklisted True to force `is_trusted` to false for the wallet
+    pub fn set_blacklisted(&mut self, wallet: Address, blacklisted: bool) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.blacklisted.setter(wallet).set(blacklisted);
+        evm::log(BlacklistUpdated { wallet, blacklisted });
+        Ok(())
+    }
+
+    /// Check whether a wallet is blacklisted
+    pub fn is_blacklisted(&self, wallet: Address) -> bool {
+        self.blacklisted.get(wallet)
+    }
+
+    /// Check whether a wallet's scores are locked against updates
+    pub fn is_frozen(&self, wallet: Address) -> bool {
+        self.frozen.get(wallet)
+    }
+
+    /// Propose a new owner (owner only); takes effect once they accept
+    /// @param new_owner The address that will become owner
+    pub fn propose_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if new_owner == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        self.pending_owner.set(new_owner);
+
+        evm::log(OwnershipTransferProposed {
+            oldOwner: self.owner.get(),
+            proposedOwner: new_owner,
+        });
+
+        Ok(())
+    }
+
+    /// Start a two-step ownership transfer (owner only); alias of `propose_ownership`
+    /// @param new_owner The address that must call `accept_ownership` to finalize
+    ///
+    /// The current owner keeps every privilege until the transfer is accepted, and
+    /// calling this again replaces the pending owner.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        self.propose_ownership(new_owner)
+    }
+
+    /// Accept a pending ownership proposal (pending owner only)
+    pub fn accept_ownership(&mut self) -> Result<(), Vec<u8>> {
+        let pending = self.pending_owner.get();
+        check_pending_owner(msg::sender(), pending)?;
+
+        let old_owner = self.owner.get();
+        self.owner.set(pending);
+        self.pending_owner.set(Address::ZERO);
+
+        // The bootstrap admin role follows ownership
+        self.set_role(ADMIN_ROLE, old_owner, false);
+        self.set_role(ADMIN_ROLE, pending, true);
+
+        evm::log(OwnershipTransferred {
+            oldOwner: old_owner,
+            newOwner: pending,
+        });
+
+        Ok(())
+    }
+
+    /// Get the address proposed as the next owner (zero if none)
+    pub fn get_pending_owner(&self) -> Address {
+        self.pending_owner.get()
+    }
+
+    /// Schedule giving up ownership for good once `RENOUNCE_TIMELOCK_SECONDS` pass (owner only)
+    pub fn propose_renounce_ownership(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let effective_at = block::timestamp() + RENOUNCE_TIMELOCK_SECONDS;
+        self.renounce_at.set(effective_at);
+
+        evm::log(OwnershipRenounceProposed { effective_at });
+
+        Ok(())
+    }
+
+    /// Set the owner to zero once the renunciation time lock expired (owner only)
+    ///
+    /// Every owner-gated function is permanently disabled afterwards, and pending
+    /// owner or oracle proposals are discarded so they cannot complete later. The
+    /// owner's `ADMIN_ROLE` is revoked; other admins must be revoked beforehand.
+    pub fn execute_renounce_ownership(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        let renounce_at = self.renounce_at.get();
+        if renounce_at == 0 {
+            return Err(NoRenouncePending {}.encode());
+        }
+
+        if block::timestamp() < renounce_at {
+            return Err(RenounceTimelockActive {}.encode());
+        }
+
+        self.renounce();
+        Ok(())
+    }
+
+    /// Permanently give up ownership right away, freezing the oracle set and all configuration (owner only)
+    ///
+    /// Sets the owner to zero in this call, like `execute_renounce_ownership` without
+    /// the time lock; use `propose_renounce_ownership` for a delayed renunciation.
+    pub fn renounce_ownership(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.renounce();
+        Ok(())
+    }
+
+    /// Abort a scheduled renunciation (owner only)
+    pub fn cancel_renounce_ownership(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if self.renounce_at.get() == 0 {
+            return Err(NoRenouncePending {}.encode());
+        }
+
+        self.renounce_at.set(0);
+
+        evm::log(OwnershipRenounceCancelled {});
+
+        Ok(())
+    }
+
+    /// Get the earliest time ownership can be renounced (0 if not proposed)
+    pub fn get_renounce_at(&self) -> u64 {
+        self.renounce_at.get()
+    }
+
+    /// Update the daily score decay rate (owner only)
+    /// @param rate_bps Basis points of the score lost per day, at most 10000
+    pub fn set_decay_rate(&mut self, rate_bps: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if rate_bps > 10_000 {
+            return Err(InvalidDecayRate {}.encode());
+        }
+
+        self.decay_rate_per_day.set(rate_bps);
+        Ok(())
+    }
+
+    /// Update the daily decay rate and the floor decay stops at (owner only)
+    /// @param rate_bps Basis points of the score lost per day, at most 10000
+    /// @param floor Minimum decayed score (0 to `max_score`); scores already below it are left as is
+    pub fn set_decay_parameters(&mut self, rate_bps: u16, floor: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if floor > self.max_score.get() {
+            return Err(InvalidScore {}.encode());
+        }
+
+        self.set_decay_rate(rate_bps)?;
+        self.decay_floor.set(floor);
+        Ok(())
+    }
+
+    /// Set the smallest score change accepted over an existing record (owner only)
+    /// @param delta Minimum absolute change in points, 0 accepts any change
+    pub fn set_min_score_delta(&mut self, delta: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.min_score_delta.set(delta);
+        Ok(())
+    }
+
+    /// Set the minimum time between consecutive updates to a record (owner only)
+    /// @param interval Seconds between score timestamps, 0 disables the check
+    pub fn set_min_update_interval(&mut self, interval: u32) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.min_update_interval.set(interval);
+        Ok(())
+    }
+
+    /// Set the largest score change accepted without a forced update (owner only)
+    /// @param delta Maximum absolute change in points, 0 removes the limit
+    pub fn set_max_score_delta(&mut self, delta: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.max_score_delta.set(delta);
+        Ok(())
+    }
+
+    /// Get the largest score change accepted without a forced update (0 = unlimited)
+    pub fn get_max_score_delta(&self) -> u16 {
+        self.max_score_delta.get()
+    }
+
+    /// Get the smallest score change accepted over an existing record
+    pub fn get_min_score_delta(&self) -> u16 {
+        self.min_score_delta.get()
+    }
+
+    /// Get the minimum seconds between consecutive updates to a record
+    pub fn get_min_update_interval(&self) -> u32 {
+        self.min_update_interval.get()
+    }
+
+    /// Set how many wallets the leaderboard keeps (owner only)
+    /// @param size Number of entries, at most `MAX_LEADERBOARD_SIZE`; 0 disables the leaderboard
+    ///
+    /// Shrinking drops the lowest entries; growing only fills up with later updates.
+    pub fn set_max_leaderboard_size(&mut self, size: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if size > MAX_LEADERBOARD_SIZE {
+            return Err(InvalidLeaderboardSize {}.encode());
+        }
+
+        self.max_leaderboard_size.set(size);
+
+        let mut entries = self.leaderboard_entries();
+        entries.truncate(size as usize);
+        self.write_leaderboard(&entries);
+        Ok(())
+    }
+
+    /// Update the score time-to-live (owner only)
+    /// @param ttl_seconds Seconds a score stays valid, 0 disables expiry
+    pub fn set_score_ttl(&mut self, ttl_seconds: u32) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.score_ttl_seconds.set(ttl_seconds);
+        Ok(())
+    }
+
+    /// Update how many history entries are kept per wallet (owner only)
+    /// @param depth Entries per wallet, 0 disables history
+    pub fn set_history_depth(&mut self, depth: u8) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        self.history_depth.set(depth);
+        Ok(())
+    }
+
+    /// Propose an additional oracle with a weight of 1 (owner only)
+    /// @param oracle The oracle signing address
+    ///
+    /// The oracle is authorized by `activate_oracle_weight` once `ORACLE_TIMELOCK_SECONDS` pass.
+    pub fn add_oracle(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if oracle == Address::ZERO {
+            return Err(InvalidAddress {}.encode());
+        }
+
+        if self.oracle_registry.get(oracle) != 0 {
+            return Err(OracleAlreadyAuthorized {}.encode());
+        }
+
+        self.propose_oracle_weight(oracle, 1);
+        Ok(())
+    }
+
+    /// Revoke an oracle's authorization (owner only)
+    /// @param oracle The oracle signing address
+    pub fn remove_oracle(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        self.deregister_oracle(oracle)
+    }
+
+    /// Check whether an address is an authorized oracle
+    pub fn is_oracle(&self, oracle: Address) -> bool {
+        self.oracle_registry.get(oracle) != 0
+    }
+
+    /// Register an oracle or change its voting weight (owner only)
+    /// @param oracle The oracle signing address
+    /// @param weight The oracle's voting weight (must be non-zero)
+    ///
+    /// Lowering a weight applies at once; registering an oracle or raising its weight
+    /// is proposed and applied by `activate_oracle_weight` once `ORACLE_TIMELOCK_SECONDS` pass.
+    pub fn register_oracle(&mut self, oracle: Address, weight: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if oracle == Address::ZERO || weight == 0 {
+            return Err(InvalidOracleWeight {}.encode());
+        }
+
+        if weight > self.oracle_registry.get(oracle) {
+            self.propose_oracle_weight(oracle, weight);
+        } else {
+            self.set_oracle_weight(oracle, weight)?;
+        }
+        Ok(())
+    }
+
+    /// Remove an oracle from the registry (owner only)
+    /// @param oracle The oracle signing address
+    pub fn deregister_oracle(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if self.oracle_registry.get(oracle) == 0 {
+            return Err(OracleNotRegistered {}.encode());
+        }
+
+        self.set_oracle_weight(oracle, 0)
+    }
+
+    /// Set the share of total oracle weight required per update (owner only)
+    /// @param quorum_threshold Percentage of total weight, 0 accepts any single registered oracle
+    pub fn set_quorum_threshold(&mut self, quorum_threshold: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if quorum_threshold > 100 {
+            return Err(InvalidQuorum {}.encode());
+        }
+
+        self.quorum_threshold.set(quorum_threshold);
+        Ok(())
+    }
+
+    /// Set how many distinct oracles must sign each update (owner only)
+    /// @param quorum Number of signers, at least 1
+    pub fn set_oracle_quorum(&mut self, quorum: u16) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+
+        if quorum == 0 {
+            return Err(InvalidQuorum {}.encode());
+        }
+
+        self.oracle_quorum.set(quorum);
+        Ok(())
+    }
+
+    /// Get the number of distinct oracles that must sign each update
+    pub fn get_oracle_quorum(&self) -> u16 {
+        self.oracle_quorum.get()
+    }
+
+    /// Get the voting weight of an oracle (zero if not registered)
+    pub fn get_oracle_weight(&self, oracle: Address) -> u16 {
+        self.oracle_registry.get(oracle)
+    }
+
+    /// Get every oracle with a non-zero weight, in no particular order
+    pub fn get_oracles(&self) -> Vec<Address> {
+        (0..self.oracle_list.len()).filter_map(|i| self.oracle_list.get(i)).collect()
+    }
+
+    /// Get the sum of all registered oracle weights
+    pub fn get_total_weight(&self) -> U256 {
+        self.total_weight.get()
+    }
+
+    /// Get the required quorum as a percentage of total weight
+    pub fn get_quorum_threshold(&self) -> u16 {
+        self.quorum_threshold.get()
+    }
+}
+
+impl TrustOracle {
+    /// Revert unless the caller is the owner
+    fn only_owner(&self) -> Result<(), Vec<u8>> {
+        check_owner(msg::sender(), self.owner.get())
+    }
+
+    /// Revert unless the caller is the primary oracle this contract mirrors
+    fn only_mirror_primary(&self) -> Result<(), Vec<u8>> {
+        let primary = self.mirror_primary.get();
+        if primary == Address::ZERO || msg::sender() != primary {
+            return Err(NotMirrorPrimary {}.encode());
+        }
+        Ok(())
+    }
+
+    /// Revert with `ContractPaused` while score mutations are paused
+    fn when_not_paused(&self) -> Result<(), Vec<u8>> {
+        check_not_paused(self.paused.get())
+    }
+
+    /// Revert if a trusted relayer submits for an oracle other than `oracle_address`
+    fn only_submitter_for(&self, oracle: Address) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        check_submitter(sender, oracle, self.oracle_address.get(), self.trusted_relayers.get(sender))
+    }
+
+    /// Revert if the relayer allowlist is enforced and the caller is not on it
+    fn only_allowlisted_relayer(&self) -> Result<(), Vec<u8>> {
+        check_allowlisted_relayer(
+            self.require_allowlisted_relayer.get(),
+            self.trusted_relayers.get(msg::sender()),
+        )
+    }
+
+    /// Take the reentrancy lock, reverting with `ReentrancyGuardTriggered` if it is already held
+    ///
+    /// A revert rolls the lock back with everything else, so only successful paths
+    /// need to call `unlock`.
+    fn lock(&mut self) -> Result<(), Vec<u8>> {
+        check_unlocked(self.reentrancy_locked.get())?;
+        self.reentrancy_locked.set(true);
+        Ok(())
+    }
+
+    /// Set the owner to zero, dropping its `ADMIN_ROLE` and every pending proposal
+    fn renounce(&mut self) {
+        let old_owner = self.owner.get();
+        self.owner.set(Address::ZERO);
+        self.set_role(ADMIN_ROLE, old_owner, false);
+        self.pending_owner.set(Address::ZERO);
+        self.pending_oracle.set(Address::ZERO);
+        self.oracle_activation_time.set(0);
+        self.renounce_at.set(0);
+
+        evm::log(OwnershipRenounced {});
+        evm::log(OwnershipTransferred {
+            oldOwner: old_owner,
+            newOwner: Address::ZERO,
+        });
+    }
+
+    /// Release the reentrancy lock taken by `lock`
+    fn unlock(&mut self) {
+        self.reentrancy_locked.set(false);
+    }
+
+    /// Revert with `InsufficientFee` unless the call pays `update_fee` for each of `updates`
+    ///
+    /// The whole attached value is kept as fees; overpayment is not refunded.
+    fn charge_update_fee(&mut self, updates: usize) -> Result<(), Vec<u8>> {
+        let paid = msg::value();
+        if paid < self.update_fee.get().saturating_mul(U256::from(updates)) {
+            return Err(InsufficientFee {}.encode());
+        }
+
+        if paid != U256::ZERO {
+            self.collected_fees.set(self.collected_fees.get() + paid);
+            evm::log(FeeCollected {
+                relayer: msg::sender(),
+                amount: paid,
+            });
+        }
+        Ok(())
+    }
+
+    /// Revert unless the caller holds `role`
+    fn only_role(&self, role: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        if !self.has_role(role, msg::sender()) {
+            return Err(MissingRole {}.encode());
+        }
+        Ok(())
+    }
+
+    /// Grant or revoke a role, logging only actual changes
+    fn set_role(&mut self, role: FixedBytes<32>, account: Address, granted: bool) {
+        if self.has_role(role, account) == granted {
+            return;
+        }
+
+        self.roles.setter(role).setter(account).set(granted);
+
+        let sender = msg::sender();
+        if granted {
+            evm::log(RoleGranted { role, account, sender });
+        } else {
+            evm::log(RoleRevoked { role, account, sender });
+        }
+    }
+
+    /// Compute the EIP-712 domain separator bound to this chain and contract address
+    fn compute_domain_separator(&self) -> FixedBytes<32> {
+        domain_separator_hash(self.domain_version.get(), block::chainid(), contract::address())
+    }
+
+    /// Domain separator for the chain currently executing
+    ///
+    /// The cached value is only reused while the chain id matches the one recorded at init,
+    /// so after a chain split signatures stay valid on exactly one side.
+    fn current_domain_separator(&self) -> FixedBytes<32> {
+        if self.chain_id.get() == U256::from(block::chainid()) {
+            self.domain_separator.get()
+        } else {
+            self.compute_domain_separator()
+        }
+    }
+
+    /// Validate, verify and store a single signed score update
+    fn apply_score_update(&mut self, update: ScoreUpdate, signatures: Vec<Vec<u8>>) -> Result<(), Vec<u8>> {
+        self.when_not_paused()?;
+
+        self.only_allowlisted_relayer()?;
+        self.validate_score_update(&update)?;
+
+        let ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadata_hash,
+            deadline,
+            valid_until,
+            ..
+        } = update;
+
+        // Verify the signatures and find the nonce they consumed
+        let (oracle, nonce) = self.verify_score_signatures(&update, signatures)?;
+
+        // Update the trust score
+        let trust_score = TrustScore {
+            score,
+            confidence,
+            timestamp,
+            validUntil: valid_until,
+            source,
+            metadataHash: metadata_hash,
+        };
+        self.store_score(wallet, category, trust_score)?;
+        
+        // Increment nonce to prevent replay
+        self.oracle_nonces
+            .setter(wallet)
+            .setter(oracle)
+            .set(nonce + U256::from(1));
+
+        // Emit event
+        evm::log(ScoreUpdated {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            metadataHash: metadata_hash,
+            deadline,
+            validUntil: valid_until,
+        });
+
+        // A fresh token per update: the (wallet, oracle, nonce) triple is never reused
+        let token_id = score_nft_token_id(wallet, oracle, nonce);
+        self.mint_score_nft(wallet, token_id, score, timestamp);
+
+        self.forward_to_mirror(wallet, category);
+
+        Ok(())
+    }
+
+    /// Copy a wallet's just-written category score to `mirror_oracle`, if one is set
+    ///
+    /// Fire-and-forget: a reverting mirror is logged as `MirrorUpdateFailed` and never
+    /// blocks the primary write. Every score write calls this after its own effects.
+    fn forward_to_mirror(&mut self, wallet: Address, category: u8) {
+        let mirror = self.mirror_oracle.get();
+        if mirror == Address::ZERO {
+            return;
+        }
+
+        let trust_score = self.read_score(wallet, category);
+
+        let result = IMirrorOracle::new(mirror).mirror_score(
+            Call::new().gas(MIRROR_CALL_GAS),
+            wallet,
+            category,
+            trust_score.score,
+            trust_score.confidence,
+            trust_score.timestamp,
+            trust_score.validUntil,
+            trust_score.source,
+            trust_score.metadataHash,
+        );
+        if result.is_err() {
+            evm::log(MirrorUpdateFailed { wallet });
+        }
+    }
+
+    /// Copy a revocation to `mirror_oracle`, if one is set, on the same terms as scores
+    fn forward_revocation_to_mirror(&mut self, wallet: Address, reason_hash: FixedBytes<32>) {
+        let mirror = self.mirror_oracle.get();
+        if mirror == Address::ZERO {
+            return;
+        }
+
+        let result = IMirrorOracle::new(mirror).mirror_revocation(
+            Call::new().gas(MIRROR_CALL_GAS),
+            wallet,
+            reason_hash,
+        );
+        if result.is_err() {
+            evm::log(MirrorUpdateFailed { wallet });
+        }
+    }
+
+    /// Mint a score attestation on `score_nft_contract`, if one is set
+    ///
+    /// A reverting mint is logged as `ScoreNftMintFailed` and otherwise ignored, so an
+    /// attestation contract can never block score updates.
+    fn mint_score_nft(&mut self, wallet: Address, token_id: U256, score: u16, timestamp: u32) {
+        let nft_contract = self.score_nft_contract.get();
+        if nft_contract == Address::ZERO {
+            return;
+        }
+
+        let result = IScoreNFT::new(nft_contract).mint(
+            Call::new().gas(SCORE_NFT_MINT_GAS),
+            wallet,
+            token_id,
+            score,
+            timestamp,
+        );
+        if result.is_err() {
+            evm::log(ScoreNftMintFailed {
+                wallet,
+                token_id,
+            });
+        }
+    }
+
+    /// Verify a score update's signatures, returning the oracle and nonce its digest binds
+    ///
+    /// Normally that is the primary oracle's quorum. During a rotation's grace period a
+    /// lone ECDSA signature from `previous_oracle` over its own nonce is accepted too, for
+    /// scores computed no later than the rotation.
+    fn verify_score_signatures(
+        &self,
+        update: &ScoreUpdate,
+        signatures: Vec<Vec<u8>>,
+    ) -> Result<(Address, U256), Vec<u8>> {
+        let oracle = self.oracle_address.get();
+        let nonce = self.oracle_nonces.get(update.wallet).get(oracle);
+        let message_hash = self.create_message_hash(update, oracle, nonce);
+
+        let single = match signatures.as_slice() {
+            [signature] => Some(signature.clone()),
+            _ => None,
+        };
+
+        let reason = match self.verify_quorum(message_hash, signatures) {
+            Ok(()) => return Ok((oracle, nonce)),
+            Err(reason) => reason,
+        };
+
+        let Some(signature) = single else {
+            return Err(reason);
+        };
+        let previous = self.previous_oracle.get();
+        if !in_rotation_grace(previous, self.rotation_expiry.get(), update.timestamp, block::timestamp()) {
+            return Err(reason);
+        }
+
+        let previous_nonce = self.oracle_nonces.get(update.wallet).get(previous);
+        let previous_hash = self.create_message_hash(update, previous, previous_nonce);
+        if self.recover_signer(previous_hash, &signature) != Some(previous) {
+            return Err(reason);
+        }
+
+        Ok((previous, previous_nonce))
+    }
+
+    /// Range, category, freeze, freshness and stored-record checks shared by every signed update
+    fn validate_score_update(&self, update: &ScoreUpdate) -> Result<(), Vec<u8>> {
+        let ScoreUpdate {
+            wallet,
+            category,
+            score,
+            confidence,
+            timestamp,
+            source,
+            deadline,
+            valid_until,
+            force,
+            ..
+        } = *update;
+
+        // Validate score and confidence range
+        check_score_range(score, confidence, self.max_score.get())?;
+
+        if ScoreCategory::from_u8(category).is_none() {
+            return Err(InvalidCategory {}.encode());
+        }
+
+        // Frozen wallets keep serving their current score but accept no writes
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        check_update_times(
+            timestamp,
+            deadline,
+            valid_until,
+            evm::block_timestamp(),
+            self.update_window(source),
+        )?;
+
+        let stored = self.read_score(wallet, category);
+        check_against_stored(
+            &stored,
+            score,
+            timestamp,
+            force,
+            self.min_score_delta.get(),
+            self.max_score_delta.get(),
+            self.min_update_interval.get(),
+        )
+    }
+
+    /// Stored score of a wallet in a category; the aggregate keeps the first release's
+    /// `trust_scores` slot, the other categories live in `trust_scores_by_category`
+    fn read_score(&self, wallet: Address, category: u8) -> TrustScore {
+        if category == ScoreCategory::Aggregate as u8 {
+            self.trust_scores.get(wallet)
+        } else {
+            self.trust_scores_by_category.get(wallet).get(category)
+        }
+    }
+
+    /// Overwrite a wallet's stored score in a category, see `read_score`
+    fn write_score(&mut self, wallet: Address, category: u8, trust_score: TrustScore) {
+        if category == ScoreCategory::Aggregate as u8 {
+            self.trust_scores.setter(wallet).set(trust_score);
+        } else {
+            self.trust_scores_by_category.setter(wallet).setter(category).set(trust_score);
+        }
+    }
+
+    /// Write a verified score to its category slot, per-source entry and history
+    fn store_score(&mut self, wallet: Address, category: u8, trust_score: TrustScore) -> Result<(), Vec<u8>> {
+        let source = trust_score.source;
+
+        // Each source keeps its own aggregate entry, capped so aggregation stays bounded.
+        // The cap is checked before any write so a batch skipping this entry leaves no trace.
+        let is_aggregate = category == ScoreCategory::Aggregate as u8;
+        let has_source_entry = is_aggregate && !is_derived_source(source);
+        let is_new_source =
+            has_source_entry && self.trust_scores_by_source.get(wallet).get(source).timestamp == 0;
+        check_source_capacity(is_new_source, self.wallet_sources.get(wallet).len())?;
+
+        if !self.wallet_indexed.get(wallet) {
+            self.wallet_indexed.setter(wallet).set(true);
+            self.scored_wallets.push(wallet);
+        }
+        self.score_exists.setter(wallet).set(true);
+        self.revoked.setter(wallet).set(false);
+
+        if is_new_source {
+            self.wallet_sources.setter(wallet).push(source);
+        }
+
+        let was_counted = self.trusted_counted.get(wallet);
+
+        // Views cannot log, so expiry of the record being replaced is reported here
+        let previous = self.read_score(wallet, category);
+        if self.is_expired(&previous) {
+            evm::log(ScoreExpired {
+                wallet,
+                expired_at: previous.timestamp.saturating_add(self.score_ttl_seconds.get()),
+            });
+        }
+
+        self.write_score(wallet, category, trust_score.clone());
+        if is_aggregate {
+            self.score_version.setter(wallet).set(1);
+        }
+
+        // Integrations watch for trust status flips rather than every score change. The
+        // status is compared with the one last counted, not re-read, since decay, expiry
+        // or a blacklisting may have changed the live status since the last write.
+        let is_now_trusted = if is_aggregate {
+            self.is_trusted_for_category(wallet, category)
+        } else {
+            was_counted
+        };
+        if is_now_trusted != was_counted {
+            self.trusted_count.set(trusted_count_after(self.trusted_count.get(), was_counted, is_now_trusted));
+            self.trusted_counted.setter(wallet).set(is_now_trusted);
+
+            evm::log(ScoreThresholdCrossed {
+                wallet,
+                is_now_trusted,
+                new_score: trust_score.score,
+                timestamp: trust_score.timestamp,
+            });
+        }
+
+        // History, per-source entries and the previous score track the aggregate score only.
+        // Scores derived from other stored scores get no per-source entry, so they take
+        // no `MAX_SOURCES_PER_WALLET` slot and never feed back into a blend.
+        if is_aggregate {
+            if previous.timestamp == 0 {
+                let total = self.total_scored_wallets.get();
+                self.total_scored_wallets.set(total + 1);
+            } else {
+                self.adjust_histogram(previous.score, false);
+            }
+            self.adjust_histogram(trust_score.score, true);
+            self.prev_scores.setter(wallet).set(previous);
+            self.update_leaderboard(wallet, Some(trust_score.score));
+            if has_source_entry {
+                self.trust_scores_by_source
+                    .setter(wallet)
+                    .setter(source)
+                    .set(trust_score.clone());
+            }
+            self.record_history(wallet, trust_score);
+        }
+
+        Ok(())
+    }
+
+    /// Verify a signed revocation and clear every score of the wallet
+    fn apply_revocation(&mut self, wallet: Address, signature: Vec<u8>) -> Result<(), Vec<u8>> {
+        self.when_not_paused()?;
+
+        if self.frozen.get(wallet) {
+            return Err(ScoreFrozen {}.encode());
+        }
+
+        let oracle = self.oracle_address.get();
+        let nonce = self.oracle_nonces.get(wallet).get(oracle);
+
+        let message_hash = self.typed_data_digest(revoke_struct_hash(wallet, oracle, nonce));
+
+        self.verify_quorum(message_hash, vec![signature])?;
+
+        self.clear_scores(wallet);
+
+        self.oracle_nonces
+            .setter(wallet)
+            .setter(oracle)
+            .set(nonce + U256::from(1));
+
+        evm::log(ScoreRevoked {
+            wallet,
+            oracle,
+            reason_hash: FixedBytes::ZERO,
+            revoked_at: evm::block_timestamp(),
+        });
+
+        self.forward_revocation_to_mirror(wallet, FixedBytes::ZERO);
+        Ok(())
+    }
+
+    /// Erase every score of a wallet and mark it revoked, keeping the counters in step
+    fn clear_scores(&mut self, wallet: Address) {
+        if self.trusted_counted.get(wallet) {
+            self.trusted_count.set(trusted_count_after(self.trusted_count.get(), true, false));
+            self.trusted_counted.setter(wallet).set(false);
+        }
+
+        let revoked = self.read_score(wallet, ScoreCategory::Aggregate as u8);
+        if revoked.timestamp != 0 {
+            self.adjust_histogram(revoked.score, false);
+            let total = self.total_scored_wallets.get();
+            self.total_scored_wallets.set(total.saturating_sub(1));
+        }
+
+        for category in ScoreCategory::ALL {
+            self.write_score(wallet, category as u8, empty_score());
+        }
+
+        self.prev_scores.setter(wallet).set(empty_score());
+        self.score_exists.setter(wallet).set(false);
+        self.trust_scores_v2.setter(wallet).set(empty_score_v2());
+        self.score_version.setter(wallet).set(0);
+        self.update_leaderboard(wallet, None);
+
+        while let Some(source) = self.wallet_sources.setter(wallet).pop() {
+            self.trust_scores_by_source
+                .setter(wallet)
+                .setter(source)
+                .set(empty_score());
+        }
+
+        self.revoked.setter(wallet).set(true);
+    }
+
+    /// Create the EIP-712 digest of a score update for signature verification
+    ///
+    /// Members are encoded in `TRUST_SCORE_TYPEHASH` order; the EIP-155 chain id and
+    /// this contract's address enter through the domain separator.
+    #[cfg(not(feature = "legacy-signing"))]
+    fn create_message_hash(&self, update: &ScoreUpdate, oracle: Address, nonce: U256) -> FixedBytes<32> {
+        self.typed_data_digest(score_struct_hash(update, oracle, nonce))
+    }
+
+    /// EIP-712 digest of a typed struct hash under this contract's domain
+    fn typed_data_digest(&self, struct_hash: FixedBytes<32>) -> FixedBytes<32> {
+        typed_data_hash(self.current_domain_separator(), struct_hash)
+    }
+
+    /// Create the pre-EIP-712 packed message hash used by older oracle backends
+    ///
+    /// The chain id and this contract's address are appended so packed signatures
+    /// cannot be replayed on another chain or another deployment either.
+    ///
+    /// Canonical layout, big-endian with no padding between fields:
+    /// wallet (20) || category (1) || score (2) || confidence (2) || timestamp (4) || source (32) ||
+    /// metadataHash (32) || deadline (4) || validUntil (4) || force (1) || oracle (20) || nonce (32) ||
+    /// chainId (32) || contract (20)
+    #[cfg(feature = "legacy-signing")]
+    fn create_message_hash(&self, update: &ScoreUpdate, oracle: Address, nonce: U256) -> FixedBytes<32> {
+        let mut message = Vec::new();
+        message.extend_from_slice(update.wallet.as_slice());
+        message.push(update.category);
+        message.extend_from_slice(&update.score.to_be_bytes());
+        message.extend_from_slice(&update.confidence.to_be_bytes());
+        message.extend_from_slice(&update.timestamp.to_be_bytes());
+        message.extend_from_slice(update.source.as_slice());
+        message.extend_from_slice(update.metadata_hash.as_slice());
+        message.extend_from_slice(&update.deadline.to_be_bytes());
+        message.extend_from_slice(&update.valid_until.to_be_bytes());
+        message.push(update.force as u8);
+        message.extend_from_slice(oracle.as_slice());
+        message.extend_from_slice(&nonce.to_be_bytes::<32>());
+        push_uint(&mut message, U256::from(block::chainid()));
+        message.extend_from_slice(contract::address().as_slice());
+        
+        keccak(message)
+    }
+
+    /// Check that enough distinct authorized oracles signed the digest: at least
+    /// `oracle_quorum` signers holding at least the quorum share of total weight.
+    fn verify_quorum(&self, message_hash: FixedBytes<32>, signatures: Vec<Vec<u8>>) -> Result<(), Vec<u8>> {
+        let mut signers = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            let signer = self
+                .resolve_signer(message_hash, &signature)
+                .ok_or_else(|| InvalidSignature {}.encode())?;
+
+            let weight = self
+                .has_role(ORACLE_ROLE, signer)
+                .then(|| self.oracle_registry.get(signer));
+            signers.push((signer, weight));
+        }
+
+        check_quorum(
+            &signers,
+            self.oracle_quorum.get(),
+            self.quorum_threshold.get(),
+            self.total_weight.get(),
+        )
+    }
+
+    /// Identify the oracle behind a signature
+    ///
+    /// When the primary oracle is a smart-contract wallet (e.g. a Safe) the signature is
+    /// checked through its ERC-1271 `isValidSignature`; otherwise ECDSA recovery is used.
+    fn resolve_signer(&self, message_hash: FixedBytes<32>, signature: &[u8]) -> Option<Address> {
+        let oracle = self.oracle_address.get();
+        if oracle.has_code() {
+            // `isValidSignature` is declared `view`, so this is a STATICCALL: the wallet
+            // can neither change state nor re-enter a write while it is consulted
+            let result = IERC1271::new(oracle).is_valid_signature(
+                Call::new(),
+                message_hash,
+                signature.to_vec().into(),
+            );
+            if erc1271_accepts(result) {
+                return Some(oracle);
+            }
+        }
+
+        self.recover_signer(message_hash, signature)
+    }
+
+    /// Recover the address that produced an ECDSA signature over the digest
+    fn recover_signer(&self, message_hash: FixedBytes<32>, signature: &[u8]) -> Option<Address> {
+        let (v, r, s) = split_signature(signature)?;
+        stylus_sdk::crypto::ecrecover(message_hash, v, r, s).ok()
+    }
+
+    /// Wallet whose score answers queries about `wallet`; delegations are not followed transitively
+    fn score_holder(&self, wallet: Address) -> Address {
+        let delegator = self.delegations.get(wallet);
+        if delegator == Address::ZERO {
+            wallet
+        } else {
+            delegator
+        }
+    }
+
+    /// Whether a wallet has an appeal that is neither resolved nor past `appeal_window_seconds`
+    fn is_appeal_open(&self, wallet: Address) -> bool {
+        let filed_at = self.appeal_filed_at.get(wallet);
+        let window = self.appeal_window_seconds.get() as u64;
+        filed_at != 0 && (window == 0 || block::timestamp() <= filed_at + window)
+    }
+
+    /// Rewrite every stored score of a wallet from the `from` scale to `to`
+    ///
+    /// Bounded by the category count, `MAX_SOURCES_PER_WALLET` and the history depth.
+    fn rescale_wallet(&mut self, wallet: Address, from: u16, to: u16) {
+        for category in ScoreCategory::ALL {
+            let stored = self.read_score(wallet, category as u8);
+            if stored.timestamp != 0 {
+                self.write_score(wallet, category as u8, rescaled(stored, from, to));
+            }
+        }
+
+        for source in self.get_wallet_sources(wallet) {
+            let stored = self.trust_scores_by_source.get(wallet).get(source);
+            self.trust_scores_by_source
+                .setter(wallet)
+                .setter(source)
+                .set(rescaled(stored, from, to));
+        }
+
+        let previous = self.prev_scores.get(wallet);
+        if previous.timestamp != 0 {
+            self.prev_scores.setter(wallet).set(rescaled(previous, from, to));
+        }
+
+        for i in 0..self.score_history.get(wallet).len() {
+            let Some(entry) = self.score_history.get(wallet).get(i) else {
+                continue;
+            };
+            if let Some(mut slot) = self.score_history.setter(wallet).setter(i) {
+                slot.set(rescaled(entry, from, to));
+            }
+        }
+    }
+
+    /// Weighted average score and confidence of a wallet's unexpired per-source scores
+    ///
+    /// Normalized by the weights of the sources actually present, so `source_weights`
+    /// need not sum to 10000. `None` if no weighted source has a score.
+    fn blend_sources(&self, wallet: Address) -> Option<TrustScore> {
+        let sources = self.wallet_sources.get(wallet);
+        let entries: Vec<(u16, TrustScore)> = (0..sources.len())
+            .filter_map(|i| sources.get(i))
+            .map(|source| (self.source_weights.get(source), self.get_trust_score_by_source(wallet, source)))
+            .collect();
+        weighted_blend(&entries, evm::block_timestamp())
+    }
+
+    /// Whether `default_score` stands in for a wallet's aggregate score: it was never
+    /// scored and not revoked, since revoked wallets get no default
+    fn is_defaulted(&self, wallet: Address) -> bool {
+        !self.score_exists.get(wallet) && !self.revoked.get(wallet)
+    }
+
+    /// Validate and store the staleness window used by sources without their own TTL
+    fn set_default_staleness_window(&mut self, max_age: u32) -> Result<(), Vec<u8>> {
+        if !(MIN_TIMESTAMP_SKEW..=MAX_TIMESTAMP_SKEW).contains(&max_age) {
+            return Err(InvalidMaxScoreAge {}.encode());
+        }
+
+        let old_max_age = self.default_staleness_window.get();
+        self.default_staleness_window.set(max_age);
+
+        evm::log(MaxScoreAgeUpdated {
+            oldMaxAge: old_max_age,
+            newMaxAge: max_age,
+        });
+
+        Ok(())
+    }
+
+    /// Maximum age in seconds of a score timestamp accepted from `source`
+    fn staleness_window(&self, source: FixedBytes<32>) -> u32 {
+        match self.source_ttl.get(source) {
+            0 => self.default_staleness_window.get(),
+            ttl => ttl,
+        }
+    }
+
+    /// Maximum age in seconds of a submitted update's timestamp from `source`: the
+    /// timestamp skew, tightened by the source's staleness window so no update lands stale
+    fn update_window(&self, source: FixedBytes<32>) -> u32 {
+        self.max_timestamp_skew.get().min(self.staleness_window(source))
+    }
+
+    /// Score reduced linearly with its age: `score * rate * age / (10000 * 1 day)`,
+    /// never below `decay_floor` unless the stored score already was
+    fn decayed_score(&self, trust_score: &TrustScore) -> u16 {
+        if trust_score.timestamp == 0 {
+            return trust_score.score;
+        }
+
+        linear_decay(
+            trust_score.score,
+            evm::block_timestamp().saturating_sub(trust_score.timestamp),
+            self.decay_rate_per_day.get(),
+            self.decay_floor.get(),
+        )
+    }
+
+    /// Whether a stored score is older than the configured TTL
+    fn is_expired(&self, trust_score: &TrustScore) -> bool {
+        let ttl = self.score_ttl_seconds.get();
+        trust_score.timestamp != 0
+            && ttl != 0
+            && evm::block_timestamp().saturating_sub(trust_score.timestamp) > ttl
+    }
+
+    /// Histogram bucket of a score: its whole percentage of `max_score`
+    fn histogram_bucket(&self, score: u16) -> usize {
+        score as usize * 100 / self.max_score.get().max(1) as usize
+    }
+
+    /// Add or remove one wallet from a score's histogram bucket
+    fn adjust_histogram(&mut self, score: u16, increment: bool) {
+        let index = self.histogram_bucket(score);
+        if let Some(mut bucket) = self.score_histogram.setter(index) {
+            let count = bucket.get();
+            bucket.set(if increment { count + 1 } else { count.saturating_sub(1) });
+        }
+    }
+
+    /// Leaderboard as (wallet, score) pairs, best first, each score at its wallet's stored scale
+    fn leaderboard_entries(&self) -> Vec<(Address, u16)> {
+        (0..self.top_wallets.len())
+            .filter_map(|i| Some((self.top_wallets.get(i)?, self.top_scores.get(i)?)))
+            .collect()
+    }
+
+    /// Move a wallet to its sorted position for a new score, or drop it with `None`
+    ///
+    /// Costs O(`max_leaderboard_size`) reads and writes, bounded by `MAX_LEADERBOARD_SIZE`.
+    fn update_leaderboard(&mut self, wallet: Address, score: Option<u16>) {
+        let size = self.max_leaderboard_size.get() as usize;
+        if size == 0 {
+            return;
+        }
+
+        let mut entries = self.leaderboard_entries();
+        entries.retain(|(entry, _)| *entry != wallet);
+
+        if let Some(score) = score {
+            // Earlier entries win ties, so a newcomer never displaces an equal score
+            let position = entries.partition_point(|(_, entry_score)| *entry_score >= score);
+            if position < size {
+                entries.insert(position, (wallet, score));
+            }
+        }
+
+        entries.truncate(size);
+        self.write_leaderboard(&entries);
+    }
+
+    /// Overwrite the stored leaderboard with `entries`
+    fn write_leaderboard(&mut self, entries: &[(Address, u16)]) {
+        let stored = self.top_wallets.len();
+        for (i, (wallet, score)) in entries.iter().enumerate() {
+            if i >= stored {
+                self.top_wallets.push(*wallet);
+                self.top_scores.push(*score);
+                continue;
+            }
+
+            if let Some(mut slot) = self.top_wallets.setter(i) {
+                slot.set(*wallet);
+            }
+            if let Some(mut slot) = self.top_scores.setter(i) {
+                slot.set(*score);
+            }
+        }
+
+        while self.top_wallets.len() > entries.len() {
+            self.top_wallets.pop();
+        }
+        while self.top_scores.len() > entries.len() {
+            self.top_scores.pop();
+        }
+    }
+
+    /// Returns (buffer length, physical head, logical index of the first retained entry)
+    ///
+    /// When the depth was lowered the buffer may still hold more entries than are
+    /// retained; the oldest surplus entries are skipped and overwritten next.
+    fn history_bounds(&self, wallet: Address) -> (u32, u32, u32) {
+        let len = self.score_history.get(wallet).len() as u32;
+        let retained = len.min(self.history_depth.get() as u32);
+        (len, self.history_head.get(wallet), len - retained)
+    }
+
+    /// Append an entry to the wallet's history ring buffer
+    ///
+    /// Once the buffer is full the oldest entry is overwritten in place, so every
+    /// update costs a bounded number of storage writes regardless of depth.
+    fn record_history(&mut self, wallet: Address, entry: TrustScore) {
+        let depth = self.history_depth.get() as u32;
+        if depth == 0 {
+            return;
+        }
+
+        let len = self.score_history.get(wallet).len() as u32;
+        let head = self.history_head.get(wallet);
+
+        if len < depth {
+            if head != 0 {
+                self.rotate_history(wallet, len, head);
+            }
+            self.score_history.setter(wallet).push(entry);
+        } else {
+            let (index, next_head) = history_overwrite(len, head);
+            if let Some(mut slot) = self.score_history.setter(wallet).setter(index) {
+                slot.set(entry);
+            }
+            self.history_head.setter(wallet).set(next_head);
+        }
+    }
+
+    /// Rewrite a wrapped buffer in chronological order so it can grow again after
+    /// the depth was raised. Bounded by the 255-entry depth cap.
+    fn rotate_history(&mut self, wallet: Address, len: u32, head: u32) {
+        let ordered: Vec<TrustScore> = (0..len)
+            .filter_map(|i| self.score_history.get(wallet).get(history_position(len, head, 0, i)))
+            .collect();
+
+        let mut history = self.score_history.setter(wallet);
+        for (i, entry) in ordered.into_iter().enumerate() {
+            if let Some(mut slot) = history.setter(i) {
+                slot.set(entry);
+            }
+        }
+        self.history_head.setter(wallet).set(0);
+    }
+
+    /// Queue an oracle weight for `activate_oracle_weight`, replacing that oracle's pending proposal
+    fn propose_oracle_weight(&mut self, oracle: Address, weight: u16) {
+        let activation_time = block::timestamp() + ORACLE_TIMELOCK_SECONDS;
+        self.oracle_activation_at.setter(oracle).set(activation_time);
+        self.pending_oracle_weights.setter(oracle).set(weight);
+
+        evm::log(OracleWeightProposed {
+            oracle,
+            weight,
+            activation_time,
+        });
+    }
+
+    /// Revert with `InsufficientStake` unless `oracle` holds `oracle_staking_requirement`
+    fn require_stake(&self, oracle: Address) -> Result<(), Vec<u8>> {
+        check_stake(self.staked_amounts.get(oracle), self.oracle_staking_requirement.get())
+    }
+
+    /// Set an oracle's weight, keeping `total_weight`, `oracle_list` and `ORACLE_ROLE` in sync
+    ///
+    /// Granting or raising a weight requires the oracle to hold the staking requirement.
+    fn set_oracle_weight(&mut self, oracle: Address, weight: u16) -> Result<(), Vec<u8>> {
+        let previous = self.oracle_registry.get(oracle);
+        if previous == weight {
+            return Ok(());
+        }
+        if weight > previous {
+            self.require_stake(oracle)?;
+        }
+
+        let total = self.total_weight.get() - U256::from(previous) + U256::from(weight);
+        self.total_weight.set(total);
+        self.oracle_registry.setter(oracle).set(weight);
+
+        if previous == 0 {
+            self.oracle_list.push(oracle);
+            self.oracle_list_index
+                .setter(oracle)
+                .set(U256::from(self.oracle_list.len()));
+            self.set_role(ORACLE_ROLE, oracle, true);
+            evm::log(OracleAdded { oracle });
+        } else if weight == 0 {
+            self.remove_from_oracle_list(oracle);
+            self.set_role(ORACLE_ROLE, oracle, false);
+            self.stake_unlock_time
+                .setter(oracle)
+                .set(block::timestamp() + STAKE_COOLDOWN_SECONDS);
+            evm::log(OracleRemoved { oracle });
+        }
+
+        evm::log(OracleWeightUpdated { oracle, weight });
+        Ok(())
+    }
+
+    /// Swap-remove an oracle from `oracle_list` in O(1) through `oracle_list_index`
+    fn remove_from_oracle_list(&mut self, oracle: Address) {
+        let len = self.oracle_list.len();
+        let stored = self.oracle_list_index.get(oracle);
+        if stored == U256::ZERO {
+            return;
+        }
+        let index = stored.to::<usize>() - 1;
+
+        if let Some(last) = swap_remove_source(index, len).and_then(|from| self.oracle_list.get(from)) {
+            if let Some(mut slot) = self.oracle_list.setter(index) {
+                slot.set(last);
+            }
+            self.oracle_list_index.setter(last).set(U256::from(index + 1));
+        }
+        self.oracle_list.pop();
+        self.oracle_list_index.setter(oracle).set(U256::ZERO);
+    }
+}
+
+/// Print the SDK's Solidity interface for the external methods (`cargo stylus export-abi`)
+///
+/// The committed `contracts/abi/ITrustOracle.sol` snapshot is generated by `build.rs`
+/// instead and checked by a test under the `stylus_interface` feature.
+#[cfg(feature = "export-abi")]
+fn main() {
+    stylus_sdk::abi::export::print_abi::<TrustOracle>("MIT", "pragma solidity ^0.8.23;");
+}
+
+/// Average score of oldest-first `history` over `[window_start, now]`, each entry weighted
+/// by how long it was in force inside the window; None if no entry covers any of it
+fn time_weighted_average(history: &[TrustScore], window_start: u32, now: u32) -> Option<u16> {
+    let mut weighted_sum = 0u64;
+    let mut total_duration = 0u64;
+    for (i, entry) in history.iter().enumerate() {
+        let end = history.get(i + 1).map_or(now, |next| next.timestamp).min(now);
+        let start = entry.timestamp.max(window_start);
+        if end <= start {
+            continue;
+        }
+
+        let duration = (end - start) as u64;
+        weighted_sum += entry.score as u64 * duration;
+        total_duration += duration;
+    }
+
+    (total_duration != 0).then(|| (weighted_sum / total_duration) as u16)
+}
+
+/// Position of the entry that a swap-remove of `index` moves into its place: the last
+/// one, unless `index` already is the last
+fn swap_remove_source(index: usize, len: usize) -> Option<usize> {
+    (index + 1 < len).then_some(len - 1)
+}
+
+/// Convert a score between scales, rounding down
+fn rescale(score: u16, from: u16, to: u16) -> u16 {
+    (score as u32 * to as u32 / from.max(1) as u32).min(u16::MAX as u32) as u16
+}
+
+/// A record with its score converted between scales
+fn rescaled(mut trust_score: TrustScore, from: u16, to: u16) -> TrustScore {
+    trust_score.score = rescale(trust_score.score, from, to);
+    trust_score
+}
+
+/// How the aggregate score moved from `previous` to `current`: (difference, previous timestamp),
+/// or (0, 0) without a previous score
+fn score_delta(previous: &TrustScore, current: &TrustScore) -> (i32, u32) {
+    if previous.timestamp == 0 {
+        return (0, 0);
+    }
+    (current.score as i32 - previous.score as i32, previous.timestamp)
+}
+
+/// The stored score, or `default_score` with a zero timestamp when the default applies
+///
+/// The zero timestamp keeps a defaulted entry distinguishable and always stale.
+fn score_or_default(stored: TrustScore, defaulted: bool, default_score: u16) -> TrustScore {
+    if defaulted {
+        return TrustScore {
+            score: default_score,
+            ..empty_score()
+        };
+    }
+    stored
+}
+
+/// Zero-valued score returned for wallets without a usable record
+fn empty_score() -> TrustScore {
+    TrustScore {
+        score: 0,
+        confidence: 0,
+        timestamp: 0,
+        validUntil: 0,
+        source: FixedBytes::ZERO,
+        metadataHash: FixedBytes::ZERO,
+    }
+}
+
+/// All-zero v2 record, served for wallets without a blob-backed score
+fn empty_score_v2() -> TrustScoreV2 {
+    TrustScoreV2 {
+        score: 0,
+        confidence: 0,
+        timestamp: 0,
+        source: FixedBytes::ZERO,
+        blobVersionedHash: FixedBytes::ZERO,
+    }
+}
+
+/// Shape a score like a Chainlink round: (roundId, answer, startedAt, updatedAt, answeredInRound)
+fn round_data(trust_score: &TrustScore) -> (U80, I256, U256, U256, U80) {
+    let round_id = U80::from(trust_score.timestamp);
+    let updated_at = U256::from(trust_score.timestamp);
+    (
+        round_id,
+        I256::try_from(trust_score.score).unwrap_or_default(),
+        updated_at,
+        updated_at,
+        round_id,
+    )
+}
+
+/// EIP-712 domain separator for a domain version hash, chain id and verifying contract
+fn domain_separator_hash(version: FixedBytes<32>, chain_id: u64, verifying_contract: Address) -> FixedBytes<32> {
+    let mut encoded = Vec::with_capacity(160);
+    encoded.extend_from_slice(EIP712_DOMAIN_TYPEHASH.as_slice());
+    encoded.extend_from_slice(keccak(DOMAIN_NAME).as_slice());
+    encoded.extend_from_slice(version.as_slice());
+    push_uint(&mut encoded, U256::from(chain_id));
+    push_address(&mut encoded, verifying_contract);
+
+    keccak(encoded)
+}
+
+/// EIP-712 digest of a struct hash under a domain separator
+fn typed_data_hash(separator: FixedBytes<32>, struct_hash: FixedBytes<32>) -> FixedBytes<32> {
+    // "\x19\x01" || domainSeparator || hashStruct(message)
+    let mut message = Vec::with_capacity(66);
+    message.extend_from_slice(b"\x19\x01");
+    message.extend_from_slice(separator.as_slice());
+    message.extend_from_slice(struct_hash.as_slice());
+
+    keccak(message)
+}
+
+/// hashStruct(TrustScore) of a score update, every member ABI-encoded to 32 bytes
+#[cfg_attr(feature = "legacy-signing", allow(dead_code))]
+fn score_struct_hash(update: &ScoreUpdate, oracle: Address, nonce: U256) -> FixedBytes<32> {
+    let mut encoded = Vec::with_capacity(384);
+    encoded.extend_from_slice(TRUST_SCORE_TYPEHASH.as_slice());
+    push_address(&mut encoded, update.wallet);
+    push_uint(&mut encoded, U256::from(update.category));
+    push_uint(&mut encoded, U256::from(update.score));
+    push_uint(&mut encoded, U256::from(update.confidence));
+    push_uint(&mut encoded, U256::from(update.timestamp));
+    encoded.extend_from_slice(update.source.as_slice());
+    encoded.extend_from_slice(update.metadata_hash.as_slice());
+    push_uint(&mut encoded, U256::from(update.deadline));
+    push_uint(&mut encoded, U256::from(update.valid_until));
+    push_uint(&mut encoded, U256::from(update.force as u8));
+    push_address(&mut encoded, oracle);
+    push_uint(&mut encoded, nonce);
+    keccak(encoded)
+}
+
+/// EIP-712 `hashStruct(ScoreBatch(...))` of a batch attestation
+///
+/// `shared` is the `(timestamp, source, metadataHash, deadline)` common to every entry.
+/// Arrays are hashed EIP-712 style: keccak of their 32-byte encoded elements.
+fn score_batch_struct_hash(
+    wallets: &[Address],
+    scores: &[u16],
+    confidences: &[u16],
+    shared: (u32, FixedBytes<32>, FixedBytes<32>, u32),
+    oracle: Address,
+    nonce: U256,
+) -> FixedBytes<32> {
+    let (timestamp, source, metadata_hash, deadline) = shared;
+    let mut wallets_encoded = Vec::with_capacity(wallets.len() * 32);
+    let mut scores_encoded = Vec::with_capacity(scores.len() * 32);
+    let mut confidences_encoded = Vec::with_capacity(confidences.len() * 32);
+    for ((wallet, score), confidence) in wallets.iter().zip(scores).zip(confidences) {
+        push_address(&mut wallets_encoded, *wallet);
+        push_uint(&mut scores_encoded, U256::from(*score));
+        push_uint(&mut confidences_encoded, U256::from(*confidence));
+    }
+
+    let mut encoded = Vec::with_capacity(320);
+    encoded.extend_from_slice(SCORE_BATCH_TYPEHASH.as_slice());
+    encoded.extend_from_slice(keccak(wallets_encoded).as_slice());
+    encoded.extend_from_slice(keccak(scores_encoded).as_slice());
+    encoded.extend_from_slice(keccak(confidences_encoded).as_slice());
+    push_uint(&mut encoded, U256::from(timestamp));
+    encoded.extend_from_slice(source.as_slice());
+    encoded.extend_from_slice(metadata_hash.as_slice());
+    push_uint(&mut encoded, U256::from(deadline));
+    push_address(&mut encoded, oracle);
+    push_uint(&mut encoded, nonce);
+    keccak(encoded)
+}
+
+/// The aggregate update an accepted appeal applies at `now`
+///
+/// Forced, since reversing a bad score is the point of an appeal.
+fn appeal_update(
+    wallet: Address,
+    score: u16,
+    confidence: u16,
+    metadata_hash: FixedBytes<32>,
+    now: u32,
+) -> ScoreUpdate {
+    ScoreUpdate {
+        wallet,
+        category: ScoreCategory::Aggregate as u8,
+        score,
+        confidence,
+        timestamp: now,
+        source: APPEAL_SOURCE,
+        metadata_hash,
+        deadline: now,
+        valid_until: 0,
+        force: true,
+    }
+}
+
+/// Score NFT token id for the update that consumed `nonce` of `oracle` for `wallet`
+fn score_nft_token_id(wallet: Address, oracle: Address, nonce: U256) -> U256 {
+    let mut encoded = Vec::with_capacity(96);
+    push_address(&mut encoded, wallet);
+    push_address(&mut encoded, oracle);
+    push_uint(&mut encoded, nonce);
+    U256::from_be_bytes(keccak(encoded).0)
+}
+
+/// EIP-712 `hashStruct(RevokeScore(wallet, oracle, nonce))`
+fn revoke_struct_hash(wallet: Address, oracle: Address, nonce: U256) -> FixedBytes<32> {
+    let mut encoded = Vec::with_capacity(128);
+    encoded.extend_from_slice(REVOKE_SCORE_TYPEHASH.as_slice());
+    push_address(&mut encoded, wallet);
+    push_address(&mut encoded, oracle);
+    push_uint(&mut encoded, nonce);
+    keccak(encoded)
+}
+
+/// Score reduced by `rate_per_day` basis points of itself per day of `age`, never below
+/// `floor` unless the score already was
+fn linear_decay(score: u16, age: u32, rate_per_day: u16, floor: u16) -> u16 {
+    let score = score as u128;
+    let decay = score * rate_per_day as u128 * age as u128 / (10_000 * 86_400);
+    let floor = score.min(floor as u128);
+
+    score.saturating_sub(decay).max(floor) as u16
+}
+
+/// Apply every entry of a skip-on-error batch
+/// @return How many entries applied, and the index and revert reason of each skipped one
+///
+/// `apply` must fail before writing anything: Stylus only rolls state back when the
+/// whole call reverts, never for an error swallowed here.
+fn apply_each<T>(
+    entries: impl IntoIterator<Item = T>,
+    mut apply: impl FnMut(T) -> Result<(), Vec<u8>>,
+) -> (u32, Vec<(usize, Vec<u8>)>) {
+    let mut applied = 0u32;
+    let mut failures = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        match apply(entry) {
+            Ok(()) => applied += 1,
+            Err(reason) => failures.push((index, reason)),
+        }
+    }
+    (applied, failures)
+}
+
+/// Revert with `TooManySources` if a new source would exceed `MAX_SOURCES_PER_WALLET`
+fn check_source_capacity(is_new_source: bool, source_count: usize) -> Result<(), Vec<u8>> {
+    if is_new_source && source_count >= MAX_SOURCES_PER_WALLET {
+        return Err(TooManySources {}.encode());
+    }
+    Ok(())
+}
+
+/// Balance the owner may recover: everything not backing an oracle stake
+fn withdrawable(balance: U256, total_staked: U256) -> U256 {
+    balance.saturating_sub(total_staked)
+}
+
+/// `trusted_count` after a wallet's counted status changes from `was_counted` to `is_counted`
+fn trusted_count_after(count: U256, was_counted: bool, is_counted: bool) -> U256 {
+    match (was_counted, is_counted) {
+        (false, true) => count + U256::from(1),
+        (true, false) => count.saturating_sub(U256::from(1)),
+        _ => count,
+    }
+}
+
+/// Whether a score counts as trusted at `now`
+/// @param blocked Whether the wallet is blacklisted, challenged or revoked, which overrides any score
+/// @param effective_score The score after decay
+fn clears_threshold(blocked: bool, trust_score: &TrustScore, effective_score: u16, threshold: u16, now: u32) -> bool {
+    if blocked || (trust_score.validUntil != 0 && now > trust_score.validUntil) {
+        return false;
+    }
+    effective_score >= threshold
+}
+
+/// Seconds since a score computed at `timestamp` was computed, 0 for no score
+fn score_age(timestamp: u32, now: u32) -> u32 {
+    if timestamp == 0 {
+        return 0;
+    }
+    now.saturating_sub(timestamp)
+}
+
+/// Whether a badge should show a score as stale: absent, or older than `max_age`
+fn is_absent_or_stale(timestamp: u32, now: u32, max_age: u32) -> bool {
+    timestamp == 0 || score_age(timestamp, now) > max_age
+}
+
+/// Whether a score computed at `timestamp` is older than `max_age` at `now`
+///
+/// A score exactly `max_age` old is still fresh.
+fn is_stale(timestamp: u32, now: u32, max_age: u32) -> bool {
+    now.saturating_sub(timestamp) > max_age
+}
+
+/// Timestamp checks on a signed update submitted at block time `now`
+/// @param max_age Timestamp skew, capped by the staleness window of the update's source
+fn check_update_times(timestamp: u32, deadline: u32, valid_until: u32, now: u32, max_age: u32) -> Result<(), Vec<u8>> {
+    // Check timestamp is within the accepted window
+    if timestamp > now || is_stale(timestamp, now, max_age) {
+        return Err(StaleTimestamp {}.encode());
+    }
+
+    // The signature itself expires independently of the score's timestamp
+    if now > deadline {
+        return Err(SignatureExpired {}.encode());
+    }
+
+    // An expiry at or before the computation time would never be trusted
+    if valid_until != 0 && valid_until <= timestamp {
+        return Err(InvalidTimestamp {}.encode());
+    }
+
+    Ok(())
+}
+
+/// URI of a metadata hash: the one linked by `set_metadata_uri`, else the base URI
+/// followed by the hash in lowercase hex
+fn metadata_uri(explicit: String, base_uri: String, metadata_hash: FixedBytes<32>) -> String {
+    if !explicit.is_empty() {
+        return explicit;
+    }
+
+    let mut uri = base_uri;
+    for byte in metadata_hash.as_slice() {
+        uri.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        uri.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    uri
+}
+
+/// Revert with `InvalidScore` unless the score is on the `max_score` scale and confidence is 0-100
+fn check_score_range(score: u16, confidence: u16, max_score: u16) -> Result<(), Vec<u8>> {
+    if score > max_score || confidence > 100 {
+        return Err(InvalidScore {}.encode());
+    }
+    Ok(())
+}
+
+/// Checks of a score copied from the primary oracle
+///
+/// Delta, rate and freshness rules were the primary's to apply; the copy only has to
+/// fit this contract's scale and arrive in order, so a delayed copy cannot roll back.
+fn check_mirrored_score(
+    stored: &TrustScore,
+    score: u16,
+    confidence: u16,
+    timestamp: u32,
+    max_score: u16,
+) -> Result<(), Vec<u8>> {
+    check_score_range(score, confidence, max_score)?;
+    check_against_stored(stored, score, timestamp, true, 0, 0, 0)
+}
+
+/// Checks of an update against the record it replaces (none for a first write)
+///
+/// Stored scores only move forward in time, and micro-updates that only spend gas
+/// and bloat the logs are rejected.
+fn check_against_stored(
+    stored: &TrustScore,
+    score: u16,
+    timestamp: u32,
+    force: bool,
+    min_delta: u16,
+    max_delta: u16,
+    min_interval: u32,
+) -> Result<(), Vec<u8>> {
+    if stored.timestamp == 0 {
+        return Ok(());
+    }
+
+    if timestamp <= stored.timestamp {
+        return Err(NonMonotonicTimestamp {}.encode());
+    }
+
+    if score.abs_diff(stored.score) < min_delta {
+        return Err(ScoreChangeInsignificant {}.encode());
+    }
+
+    // Circuit breaker against a faulty model swinging scores wholesale
+    if !force && max_delta != 0 && score.abs_diff(stored.score) > max_delta {
+        return Err(ScoreDeltaTooLarge {}.encode());
+    }
+
+    if timestamp.saturating_sub(stored.timestamp) < min_interval {
+        return Err(UpdateTooFrequent {}.encode());
+    }
+    Ok(())
+}
+
+/// Whether an `isValidSignature` call approved the signature: it returned the magic value
+fn erc1271_accepts<E>(result: Result<FixedBytes<4>, E>) -> bool {
+    matches!(result, Ok(magic) if magic == ERC1271_MAGIC_VALUE)
+}
+
+/// Split a 65-byte `r || s || v` signature into the `(v, r, s)` handed to ecrecover
+///
+/// `v` is normalised to 27/28 from either encoding. Degenerate (zero) `r`/`s` and the
+/// malleable high-s twin are rejected, so each signature has exactly one accepted form.
+fn split_signature(signature: &[u8]) -> Option<(u8, FixedBytes<32>, FixedBytes<32>)> {
+    if signature.len() != 65 {
+        return None;
+    }
+
+    let r = FixedBytes::<32>::from_slice(&signature[0..32]);
+    let s = FixedBytes::<32>::from_slice(&signature[32..64]);
+    // Accept both the 0/1 and 27/28 recovery id encodings
+    let v = match signature[64] {
+        v @ (0 | 1) => v + 27,
+        v @ (27 | 28) => v,
+        _ => return None,
+    };
+
+    // Degenerate signatures are rejected before paying for the precompile
+    if r == FixedBytes::ZERO || s == FixedBytes::ZERO {
+        return None;
+    }
+
+    // Only the low-s form of a signature is accepted
+    if U256::from_be_bytes(s.0) > SECP256K1N_HALF {
+        return None;
+    }
+
+    Some((v, r, s))
+}
+
+/// Check recovered signers against the oracle set and both quorum rules
+/// @param signers Each signer with its oracle weight, `None` when it lacks `ORACLE_ROLE`
+/// @param min_signers Distinct oracles that must sign (`oracle_quorum`)
+/// @param threshold_percent Share of `total_weight` the signers must hold (`quorum_threshold`)
+fn check_quorum(
+    signers: &[(Address, Option<u16>)],
+    min_signers: u16,
+    threshold_percent: u16,
+    total_weight: U256,
+) -> Result<(), Vec<u8>> {
+    let mut seen: Vec<Address> = Vec::with_capacity(signers.len());
+    let mut summed_weight = U256::ZERO;
+
+    for &(signer, weight) in signers {
+        let weight = weight.ok_or_else(|| UnauthorizedOracle {}.encode())?;
+
+        if seen.contains(&signer) {
+            return Err(DuplicateOracleSigner {}.encode());
+        }
+
+        seen.push(signer);
+        summed_weight += U256::from(weight);
+    }
+
+    if seen.len() < min_signers as usize {
+        return Err(QuorumNotMet {}.encode());
+    }
+
+    if summed_weight * U256::from(100) < total_weight * U256::from(threshold_percent) {
+        return Err(QuorumNotMet {}.encode());
+    }
+
+    Ok(())
+}
+
+/// Revert with `MissingRole` unless `sender` holds `PAUSER_ROLE` or is the guardian
+///
+/// The guardian may only pause; unpausing and configuration stay with role holders.
+fn check_pauser(sender: Address, is_pauser: bool, guardian: Address) -> Result<(), Vec<u8>> {
+    if !is_pauser && (guardian == Address::ZERO || sender != guardian) {
+        return Err(MissingRole {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `UnauthorizedOracle` if a trusted relayer names an oracle other than `primary`
+///
+/// The primary oracle submitting its own update needs no `oracle` argument. Any other
+/// caller is left to the relayer allowlist and the signature checks.
+fn check_submitter(sender: Address, oracle: Address, primary: Address, is_relayer: bool) -> Result<(), Vec<u8>> {
+    if sender != primary && is_relayer && oracle != primary {
+        return Err(UnauthorizedOracle {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `ReentrancyGuardTriggered` if the reentrancy lock is already held
+fn check_unlocked(locked: bool) -> Result<(), Vec<u8>> {
+    if locked {
+        return Err(ReentrancyGuardTriggered {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `ContractPaused` if `paused` is set
+fn check_not_paused(paused: bool) -> Result<(), Vec<u8>> {
+    if paused {
+        return Err(ContractPaused {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `UnauthorizedRelayer` if the allowlist is enforced and the caller is not on it
+fn check_allowlisted_relayer(required: bool, allowlisted: bool) -> Result<(), Vec<u8>> {
+    if required && !allowlisted {
+        return Err(UnauthorizedRelayer {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `NotOwner` unless `sender` is `owner`
+///
+/// Nobody passes once ownership was renounced to the zero address.
+fn check_owner(sender: Address, owner: Address) -> Result<(), Vec<u8>> {
+    if owner == Address::ZERO || sender != owner {
+        return Err(NotOwner {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `NotPendingOwner` unless `sender` is the proposed owner
+fn check_pending_owner(sender: Address, pending: Address) -> Result<(), Vec<u8>> {
+    if pending == Address::ZERO || sender != pending {
+        return Err(NotPendingOwner {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert with `InsufficientStake` if `staked` is below the `required` oracle stake
+fn check_stake(staked: U256, required: U256) -> Result<(), Vec<u8>> {
+    if staked < required {
+        return Err(InsufficientStake {}.encode());
+    }
+    Ok(())
+}
+
+/// Revert unless an oracle proposal is pending and its time lock has run out at `now`
+fn check_oracle_activation(pending: Address, activation_time: u64, now: u64) -> Result<(), Vec<u8>> {
+    if pending == Address::ZERO {
+        return Err(NoPendingOracle {}.encode());
+    }
+
+    if now < activation_time {
+        return Err(OracleTimelockActive {}.encode());
+    }
+    Ok(())
+}
+
+/// Whether a retired oracle's signature over a score computed at `timestamp` still verifies
+///
+/// Only until `rotation_expiry`, and only for scores computed before the rotation, which
+/// happened `ORACLE_ROTATION_GRACE_SECONDS` before the expiry: a retired key cannot sign
+/// new scores during the window.
+fn in_rotation_grace(previous: Address, rotation_expiry: u64, timestamp: u32, now: u64) -> bool {
+    let rotated_at = rotation_expiry.saturating_sub(ORACLE_ROTATION_GRACE_SECONDS);
+    previous != Address::ZERO && now <= rotation_expiry && timestamp as u64 <= rotated_at
+}
+
+/// Whether `source` marks a score computed on-chain from the wallet's other scores
+fn is_derived_source(source: FixedBytes<32>) -> bool {
+    source == COMPOSITE_SOURCE || source == BLEND_SOURCE
+}
+
+/// Weighted average of `(weight, score)` entries, as a score no fresher than its inputs
+///
+/// Zero-weight, never-written and, at `now`, expired entries are skipped; `None` if
+/// nothing remains. The result carries the oldest input timestamp and the earliest
+/// input expiry, and a zero source and metadata hash for the caller to fill in.
+fn weighted_blend(entries: &[(u16, TrustScore)], now: u32) -> Option<TrustScore> {
+    let mut weighted_sum = 0u64;
+    let mut weighted_confidence = 0u64;
+    let mut total_weight = 0u64;
+    let mut timestamp = u32::MAX;
+    let mut valid_until = 0u32;
+
+    for (weight, trust_score) in entries {
+        let weight = *weight as u64;
+        let expired = trust_score.validUntil != 0 && now > trust_score.validUntil;
+        if weight == 0 || trust_score.timestamp == 0 || expired {
+            continue;
+        }
+
+        weighted_sum += trust_score.score as u64 * weight;
+        weighted_confidence += trust_score.confidence as u64 * weight;
+        total_weight += weight;
+        timestamp = timestamp.min(trust_score.timestamp);
+        if trust_score.validUntil != 0 && (valid_until == 0 || trust_score.validUntil < valid_until) {
+            valid_until = trust_score.validUntil;
+        }
+    }
+
+    if total_weight == 0 {
+        return None;
+    }
+    Some(TrustScore {
+        score: (weighted_sum / total_weight) as u16,
+        confidence: (weighted_confidence / total_weight) as u16,
+        timestamp,
+        validUntil: valid_until,
+        ..empty_score()
+    })
+}
+
+/// Slot a full history ring overwrites next, and the head that follows it
+///
+/// The head always points at the oldest retained entry, so overwriting it evicts
+/// exactly that entry and advances the head to the next-oldest.
+fn history_overwrite(len: u32, head: u32) -> (u32, u32) {
+    (head, (head + 1) % len)
+}
+
+/// Physical slot of the `index`-th oldest retained history entry
+fn history_position(len: u32, head: u32, start: u32, index: u32) -> u32 {
+    (head + start + index) % len
+}
+
+/// Leaf of a score Merkle tree: `keccak256(keccak256(abi.encode(wallet, score, confidence, timestamp, source, metadataHash)))`
+///
+/// Hashing twice keeps a leaf from being mistaken for an inner node.
+fn merkle_leaf(
+    wallet: Address,
+    score: u16,
+    confidence: u16,
+    timestamp: u32,
+    source: FixedBytes<32>,
+    metadata_hash: FixedBytes<32>,
+) -> FixedBytes<32> {
+    let mut encoded = Vec::with_capacity(192);
+    push_address(&mut encoded, wallet);
+    push_uint(&mut encoded, U256::from(score));
+    push_uint(&mut encoded, U256::from(confidence));
+    push_uint(&mut encoded, U256::from(timestamp));
+    encoded.extend_from_slice(source.as_slice());
+    encoded.extend_from_slice(metadata_hash.as_slice());
+    keccak(keccak(encoded))
+}
+
+/// Fold a Merkle proof into the root it implies, hashing each pair in sorted order
+fn process_proof(leaf: FixedBytes<32>, proof: &[FixedBytes<32>]) -> FixedBytes<32> {
+    proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+
+        let mut pair = Vec::with_capacity(64);
+        pair.extend_from_slice(left.as_slice());
+        pair.extend_from_slice(right.as_slice());
+        keccak(pair)
+    })
+}
+
+/// Append an address as a left-padded 32-byte ABI word
+fn push_address(buf: &mut Vec<u8>, value: Address) {
+    buf.extend_from_slice(&[0u8; 12]);
+    buf.extend_from_slice(value.as_slice());
+}
+
+/// Append an unsigned integer as a 32-byte big-endian ABI word
+fn push_uint(buf: &mut Vec<u8>, value: U256) {
+    buf.extend_from_slice(&value.to_be_bytes::<32>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assert each unit error encodes to exactly its selector and decodes back
+    /// Round-trip each parameterless error and return the names it checked
+    macro_rules! assert_error_round_trip {
+        ($($error:ident),* $(,)?) => {{
+            $(
+                let encoded = $error {}.encode();
+                assert_eq!(encoded[..], keccak(concat!(stringify!($error), "()"))[..4]);
+                assert!($error::decode(&encoded, true).is_ok());
+            )*
+            [$(stringify!($error)),*]
+        }};
+    }
+
+    /// (name, parameter list) of every error in the `sol!` block, in declaration order
+    fn declared_errors() -> Vec<(&'static str, &'static str)> {
+        let source = include_str!("main.rs");
+        let contract = source.split("#[cfg(test)]").next().unwrap();
+        contract
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("error "))
+            .map(|declaration| {
+                let (name, params) = declaration.split_once('(').unwrap();
+                (name, params.trim_end_matches(");"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn typehashes_match_their_type_strings() {
+        assert_eq!(
+            EIP712_DOMAIN_TYPEHASH,
+            keccak(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+        );
+        assert_eq!(
+            TRUST_SCORE_TYPEHASH,
+            keccak(
+                b"TrustScore(address wallet,uint8 category,uint16 score,uint16 confidence,uint32 timestamp,\
+                  bytes32 source,bytes32 metadataHash,uint32 deadline,uint32 validUntil,bool force,address oracle,uint256 nonce)"
+            )
+        );
+    }
+
+    fn verifying_contract() -> Address {
+        Address::repeat_byte(0xc0)
+    }
+
+    #[test]
+    fn digest_is_bound_to_the_chain_id() {
+        let version = keccak(DOMAIN_VERSION);
+        let struct_hash = keccak(b"score update");
+
+        let arbitrum = domain_separator_hash(version, 42_161, verifying_contract());
+        let nova = domain_separator_hash(version, 42_170, verifying_contract());
+        assert_ne!(arbitrum, nova);
+        // ecrecover over a different digest yields a different signer, so a signature
+        // accepted on one chain cannot verify on the other
+        assert_ne!(typed_data_hash(arbitrum, struct_hash), typed_data_hash(nova, struct_hash));
+        assert_eq!(
+            typed_data_hash(arbitrum, struct_hash),
+            typed_data_hash(domain_separator_hash(version, 42_161, verifying_contract()), struct_hash)
+        );
+    }
+
+    #[test]
+    fn digest_is_bound_to_the_verifying_contract() {
+        let version = keccak(DOMAIN_VERSION);
+        let struct_hash = keccak(b"score update");
+
+        let instance_a = domain_separator_hash(version, 42_161, Address::repeat_byte(0xaa));
+        let instance_b = domain_separator_hash(version, 42_161, Address::repeat_byte(0xbb));
+        assert_ne!(typed_data_hash(instance_a, struct_hash), typed_data_hash(instance_b, struct_hash));
+    }
+
+    fn oracle(n: u8) -> Address {
+        Address::repeat_byte(n)
+    }
+
+    #[test]
+    fn newly_added_oracle_signature_is_accepted() {
+        // Oracle 2 was added next to oracle 1: both hold ORACLE_ROLE with weight 1
+        assert_eq!(check_quorum(&[(oracle(2), Some(1))], 1, 0, U256::from(2)), Ok(()));
+    }
+
+    #[test]
+    fn removed_oracle_signature_is_rejected() {
+        // After removal the signer no longer holds ORACLE_ROLE
+        assert_eq!(
+            check_quorum(&[(oracle(2), None)], 1, 0, U256::from(1)),
+            Err(UnauthorizedOracle {}.encode())
+        );
+    }
+
+    #[test]
+    fn exactly_quorum_signers_are_accepted() {
+        let signers = [(oracle(1), Some(1)), (oracle(2), Some(1))];
+        assert_eq!(check_quorum(&signers, 2, 50, U256::from(3)), Ok(()));
+    }
+
+    #[test]
+    fn below_quorum_is_rejected() {
+        let signers = [(oracle(1), Some(1))];
+        // Too few distinct signers
+        assert_eq!(check_quorum(&signers, 2, 0, U256::from(3)), Err(QuorumNotMet {}.encode()));
+        // Enough signers but not enough of the total weight
+        assert_eq!(check_quorum(&signers, 1, 50, U256::from(3)), Err(QuorumNotMet {}.encode()));
+    }
+
+    #[test]
+    fn duplicate_signer_is_rejected() {
+        let signers = [(oracle(1), Some(1)), (oracle(1), Some(1))];
+        assert_eq!(
+            check_quorum(&signers, 2, 0, U256::from(2)),
+            Err(DuplicateOracleSigner {}.encode())
+        );
+    }
+
+    /// A well-formed low-s signature with the given recovery byte
+    fn signature(v: u8) -> Vec<u8> {
+        let mut signature = vec![0x11; 32];
+        signature.extend_from_slice(&U256::from(0x1234_5678u64).to_be_bytes::<32>());
+        signature.push(v);
+        signature
+    }
+
+    #[test]
+    fn malleable_high_s_twin_is_rejected() {
+        let canonical = signature(27);
+        let (v, r, s) = split_signature(&canonical).unwrap();
+        assert_eq!(v, 27);
+
+        // The twin (r, n - s, v ^ 1) recovers the same signer from the same digest
+        let curve_order = SECP256K1N_HALF * U256::from(2) + U256::from(1);
+        let twin_s = curve_order - U256::from_be_bytes(s.0);
+        let mut twin = r.to_vec();
+        twin.extend_from_slice(&twin_s.to_be_bytes::<32>());
+        twin.push(28);
+
+        assert_eq!(split_signature(&twin), None);
+    }
+
+    #[test]
+    fn s_at_half_curve_order_is_accepted() {
+        let mut signature = signature(27);
+        signature[32..64].copy_from_slice(&SECP256K1N_HALF.to_be_bytes::<32>());
+        assert!(split_signature(&signature).is_some());
+
+        signature[32..64].copy_from_slice(&(SECP256K1N_HALF + U256::from(1)).to_be_bytes::<32>());
+        assert_eq!(split_signature(&signature), None);
+    }
+
+    #[test]
+    fn both_recovery_id_encodings_recover_alike() {
+        assert_eq!(split_signature(&signature(0)), split_signature(&signature(27)));
+        assert_eq!(split_signature(&signature(1)), split_signature(&signature(28)));
+        assert_eq!(split_signature(&signature(1)).unwrap().0, 28);
+    }
+
+    #[test]
+    fn recovery_id_outside_both_encodings_is_rejected() {
+        for v in [2, 26, 29, 255] {
+            assert_eq!(split_signature(&signature(v)), None);
+        }
+    }
+
+    #[test]
+    fn degenerate_r_or_s_is_rejected_in_either_encoding() {
+        for v in [0, 27] {
+            assert!(split_signature(&signature(v)).is_some());
+
+            let mut zero_r = signature(v);
+            zero_r[0..32].fill(0);
+            assert_eq!(split_signature(&zero_r), None);
+
+            let mut zero_s = signature(v);
+            zero_s[32..64].fill(0);
+            assert_eq!(split_signature(&zero_s), None);
+        }
+    }
+
+    const NOW: u32 = 1_700_000_000;
+
+    #[test]
+    fn expired_deadline_is_rejected() {
+        assert_eq!(
+            check_update_times(NOW - 10, NOW - 1, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            Err(SignatureExpired {}.encode())
+        );
+    }
+
+    #[test]
+    fn old_timestamp_with_live_deadline_is_accepted() {
+        // Computed 50 minutes ago and queued since, but the signature is still live
+        assert_eq!(check_update_times(NOW - 3000, NOW + 600, 0, NOW, DEFAULT_MAX_SCORE_AGE), Ok(()));
+        // The deadline itself is inclusive
+        assert_eq!(check_update_times(NOW - 3000, NOW, 0, NOW, DEFAULT_MAX_SCORE_AGE), Ok(()));
+    }
+
+    #[test]
+    fn erc1271_magic_value_matches_the_selector() {
+        assert_eq!(ERC1271_MAGIC_VALUE[..], keccak(b"isValidSignature(bytes32,bytes)")[..4]);
+    }
+
+    #[test]
+    fn erc1271_check_is_a_static_call() {
+        // A mutating declaration would let the oracle wallet write state mid-update
+        let source = include_str!("main.rs");
+        let interface = &source[source.find("interface IERC1271 {").unwrap()..];
+        let declaration = interface.lines().nth(1).unwrap();
+        assert!(declaration.contains("function isValidSignature("));
+        assert!(declaration.contains(" external view "));
+    }
+
+    #[test]
+    fn erc1271_wallet_approval_requires_the_magic_value() {
+        // A mock wallet returning the magic value approves
+        assert!(erc1271_accepts::<Vec<u8>>(Ok(ERC1271_MAGIC_VALUE)));
+        // Any other return value, or a revert, does not
+        assert!(!erc1271_accepts::<Vec<u8>>(Ok(fixed_bytes!("ffffffff"))));
+        assert!(!erc1271_accepts::<Vec<u8>>(Ok(FixedBytes::ZERO)));
+        assert!(!erc1271_accepts(Err(Vec::<u8>::new())));
+    }
+
+    #[test]
+    fn mixed_batch_applies_valid_entries_and_reports_the_rest() {
+        let entries = [
+            check_update_times(NOW - 10, NOW + 60, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            check_update_times(NOW - 10, NOW - 1, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            check_update_times(NOW - 20, NOW + 60, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            check_update_times(NOW + 5, NOW + 60, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+        ];
+
+        let mut written = Vec::new();
+        let (applied, failures) = apply_each(entries.into_iter().enumerate(), |(index, outcome)| {
+            outcome?;
+            written.push(index);
+            Ok(())
+        });
+
+        assert_eq!(applied, 2);
+        assert_eq!(written, [0, 2]);
+        assert_eq!(
+            failures,
+            [(1, SignatureExpired {}.encode()), (3, StaleTimestamp {}.encode())]
+        );
+    }
+
+    #[test]
+    fn source_cap_only_blocks_new_sources() {
+        assert_eq!(check_source_capacity(true, MAX_SOURCES_PER_WALLET - 1), Ok(()));
+        assert_eq!(
+            check_source_capacity(true, MAX_SOURCES_PER_WALLET),
+            Err(TooManySources {}.encode())
+        );
+        // A full wallet still accepts updates from a source it already has
+        assert_eq!(check_source_capacity(false, MAX_SOURCES_PER_WALLET), Ok(()));
+    }
+
+    #[test]
+    fn error_selectors_round_trip() {
+        let checked = assert_error_round_trip!(
+            InvalidSignature,
+            UnauthorizedOracle,
+            InvalidScore,
+            StaleTimestamp,
+            AlreadyInitialized,
+            NotOwner,
+            NotPendingOwner,
+            InvalidAddress,
+            InvalidThreshold,
+            InvalidCategory,
+            InvalidQuorum,
+            InvalidOracleWeight,
+            OracleAlreadyAuthorized,
+            OracleNotRegistered,
+            DuplicateOracleSigner,
+            QuorumNotMet,
+            SignatureExpired,
+            ContractPaused,
+            BatchLengthMismatch,
+            HistoryIndexOutOfRange,
+            BatchTooLarge,
+            InvalidDecayRate,
+            ScoreFrozen,
+            TooManySources,
+            DelegationExists,
+            NoDelegation,
+            NoPendingOracle,
+            InvalidMaxScoreAge,
+            InvalidTimestampSkew,
+            MerkleRootExpired,
+            NoRenouncePending,
+            RenounceTimelockActive,
+            NotChallenger,
+            MissingRole,
+            ScoreChangeInsignificant,
+            UpdateTooFrequent,
+            ScoreDeltaTooLarge,
+            UnauthorizedRelayer,
+            InvalidLeaderboardSize,
+            NonMonotonicTimestamp,
+            InvalidTimestamp,
+            IndexOutOfRange,
+            NoCategoryScores,
+            InvalidCategoryWeight,
+            InvalidSourceWeight,
+            InvalidBlobHash,
+            InsufficientStake,
+            InsufficientFee,
+            StakeLocked,
+            AppealPending,
+            NoOpenAppeal,
+            ReentrancyGuardTriggered,
+            NotMirrorPrimary,
+            InvalidScorePrecision,
+            NoSourceScores,
+            NoStake,
+            NotPermitRequester,
+            InvalidMerkleProof,
+            OracleTimelockActive,
+        );
+
+        // The one error with a parameter
+        let encoded = WalletNotTrusted { wallet: oracle(1) }.encode();
+        assert_eq!(encoded[..4], keccak(b"WalletNotTrusted(address)")[..4]);
+        assert_eq!(WalletNotTrusted::decode(&encoded, true).unwrap().wallet, oracle(1));
+
+        // Every declared error is covered, so a new one cannot be added without a check
+        let mut covered: Vec<&str> = checked.to_vec();
+        covered.push("WalletNotTrusted");
+        let mut declared: Vec<&str> = declared_errors().into_iter().map(|(name, _)| name).collect();
+        covered.sort_unstable();
+        declared.sort_unstable();
+        assert_eq!(covered, declared);
+    }
+
+    #[test]
+    fn every_declared_error_is_raised() {
+        let source = include_str!("main.rs");
+        let contract = source.split("#[cfg(test)]").next().unwrap();
+        for (name, _) in declared_errors() {
+            let construction = [name, " {"].concat();
+            assert!(contract.contains(&construction), "{name} is declared but never raised");
+        }
+    }
+
+    const DAY: u32 = 86_400;
+
+    #[test]
+    fn fresh_score_does_not_decay() {
+        assert_eq!(linear_decay(80, 0, 1_000, 0), 80);
+        // A zero rate disables decay at any age
+        assert_eq!(linear_decay(80, 365 * DAY, 0, 0), 80);
+    }
+
+    #[test]
+    fn score_decays_linearly_with_age() {
+        // 10% of the score per day: half gone after five days
+        assert_eq!(linear_decay(80, 5 * DAY, 1_000, 0), 40);
+        assert_eq!(linear_decay(80, DAY / 2, 1_000, 0), 76);
+    }
+
+    #[test]
+    fn fully_decayed_score_floors() {
+        assert_eq!(linear_decay(80, 10 * DAY, 1_000, 0), 0);
+        assert_eq!(linear_decay(80, 30 * DAY, 1_000, 0), 0);
+        assert_eq!(linear_decay(80, 30 * DAY, 1_000, 25), 25);
+        // The floor never raises a score that started below it
+        assert_eq!(linear_decay(10, 30 * DAY, 1_000, 25), 10);
+    }
+
+    #[test]
+    fn revocation_is_signed_over_the_current_nonce() {
+        assert_eq!(REVOKE_SCORE_TYPEHASH, keccak(b"RevokeScore(address wallet,address oracle,uint256 nonce)"));
+
+        // Revoking bumps the nonce, so the same signature cannot revoke again
+        let wallet = Address::repeat_byte(0x01);
+        let first = revoke_struct_hash(wallet, oracle(1), U256::ZERO);
+        assert_ne!(first, revoke_struct_hash(wallet, oracle(1), U256::from(1)));
+        assert_ne!(first, revoke_struct_hash(Address::repeat_byte(0x02), oracle(1), U256::ZERO));
+    }
+
+    #[test]
+    fn lowered_depth_hides_the_oldest_entries() {
+        // A ring of five read back with depth two: only the newest two remain visible
+        let (ring, head) = ([6u16, 7, 3, 4, 5], 2u32);
+        let (len, start) = (5u32, 5 - 2);
+        let retained: Vec<u16> = (0..len - start).map(|i| ring[history_position(len, head, start, i) as usize]).collect();
+        assert_eq!(retained, vec![6, 7]);
+    }
+
+    fn source_score(score: u16, confidence: u16) -> TrustScore {
+        TrustScore {
+            score,
+            confidence,
+            timestamp: NOW,
+            ..empty_score()
+        }
+    }
+
+    fn blended(entries: &[(u16, TrustScore)]) -> Option<(u16, u16)> {
+        weighted_blend(entries, NOW).map(|blended| (blended.score, blended.confidence))
+    }
+
+    #[test]
+    fn aggregate_weights_each_source() {
+        // A 3:1 weighting pulls the average towards the heavier source
+        let entries = [(7_500, source_score(80, 90)), (2_500, source_score(40, 50))];
+        assert_eq!(blended(&entries), Some((70, 80)));
+
+        // Weights need not sum to 10000: only their ratio matters
+        let entries = [(3, source_score(80, 90)), (1, source_score(40, 50))];
+        assert_eq!(blended(&entries), Some((70, 80)));
+    }
+
+    #[test]
+    fn aggregate_skips_unweighted_and_missing_sources() {
+        let entries = [(0, source_score(10, 10)), (5_000, empty_score()), (1, source_score(60, 70))];
+        assert_eq!(blended(&entries), Some((60, 70)));
+        assert_eq!(blended(&entries[..2]), None);
+        assert_eq!(blended(&[]), None);
+    }
+
+    #[test]
+    fn oracle_activates_only_after_the_time_lock() {
+        let proposed_at = NOW as u64;
+        let activation_time = proposed_at + ORACLE_TIMELOCK_SECONDS;
+
+        assert_eq!(
+            check_oracle_activation(oracle(1), activation_time, proposed_at),
+            Err(OracleTimelockActive {}.encode())
+        );
+        assert_eq!(
+            check_oracle_activation(oracle(1), activation_time, activation_time - 1),
+            Err(OracleTimelockActive {}.encode())
+        );
+
+        // Travel past the 24-hour window
+        assert_eq!(check_oracle_activation(oracle(1), activation_time, activation_time), Ok(()));
+        assert_eq!(check_oracle_activation(oracle(1), activation_time, proposed_at + 2 * DAY as u64), Ok(()));
+    }
+
+    #[test]
+    fn cancelled_proposal_cannot_be_activated() {
+        assert_eq!(
+            check_oracle_activation(Address::ZERO, 0, NOW as u64),
+            Err(NoPendingOracle {}.encode())
+        );
+    }
+
+    #[test]
+    fn score_is_fresh_up_to_exactly_max_age() {
+        let max_age = DEFAULT_MAX_SCORE_AGE;
+        assert!(!is_stale(NOW, NOW, max_age));
+        assert!(!is_stale(NOW - max_age, NOW, max_age));
+        assert!(is_stale(NOW - max_age - 1, NOW, max_age));
+
+        // The view and update_score draw the line in the same place
+        assert_eq!(check_update_times(NOW - max_age, NOW, 0, NOW, max_age), Ok(()));
+        assert_eq!(
+            check_update_times(NOW - max_age - 1, NOW, 0, NOW, max_age),
+            Err(StaleTimestamp {}.encode())
+        );
+    }
+
+    #[test]
+    fn widened_window_accepts_a_formerly_stale_timestamp() {
+        // A daily feed's score computed 20 hours ago
+        let timestamp = NOW - 20 * 3_600;
+        assert_eq!(
+            check_update_times(timestamp, NOW, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            Err(StaleTimestamp {}.encode())
+        );
+
+        let widened = DAY;
+        assert!((MIN_TIMESTAMP_SKEW..=MAX_TIMESTAMP_SKEW).contains(&widened));
+        assert_eq!(check_update_times(timestamp, NOW, 0, NOW, widened), Ok(()));
+    }
+
+    #[test]
+    fn tight_skew_rejects_updates_a_long_ttl_would_allow() {
+        // A high-frequency feed on a five minute skew, with a source TTL of one day
+        let (skew, ttl) = (300, DAY);
+        let window = std::cmp::min(skew, ttl);
+        assert_eq!(check_update_times(NOW - 240, NOW, 0, NOW, window), Ok(()));
+        assert_eq!(check_update_times(NOW - 600, NOW, 0, NOW, window), Err(StaleTimestamp {}.encode()));
+        assert!((MIN_TIMESTAMP_SKEW..=MAX_TIMESTAMP_SKEW).contains(&DEFAULT_MAX_TIMESTAMP_SKEW));
+    }
+
+    #[test]
+    fn only_the_pending_owner_can_accept() {
+        let proposed = Address::repeat_byte(0xB2);
+        assert_eq!(
+            check_pending_owner(Address::repeat_byte(0xC3), proposed),
+            Err(NotPendingOwner {}.encode())
+        );
+        // Nothing to accept once the slot is cleared
+        assert_eq!(
+            check_pending_owner(Address::ZERO, Address::ZERO),
+            Err(NotPendingOwner {}.encode())
+        );
+    }
+
+    #[test]
+    fn claimed_leaf_must_prove_into_the_root() {
+        let leaf = |score| merkle_leaf(Address::repeat_byte(0x01), score, 90, NOW, FixedBytes::ZERO, FixedBytes::ZERO);
+        let sibling = merkle_leaf(Address::repeat_byte(0x02), 40, 80, NOW, FixedBytes::ZERO, FixedBytes::ZERO);
+        let root = process_proof(leaf(70), &[sibling]);
+
+        assert_eq!(process_proof(sibling, &[leaf(70)]), root);
+        // A leaf with an altered score proves into a different root
+        assert_ne!(process_proof(leaf(95), &[sibling]), root);
+    }
+
+    #[test]
+    fn claim_deadline_is_the_root_expiry() {
+        // claim_score checks timestamps with the root's expiry as the deadline
+        let root_valid_until = NOW + 600;
+        assert_eq!(check_update_times(NOW - 60, root_valid_until, 0, NOW, DEFAULT_MAX_SCORE_AGE), Ok(()));
+        assert_eq!(
+            check_update_times(NOW - 2 * DAY, root_valid_until, 0, NOW, DEFAULT_MAX_SCORE_AGE),
+            Err(StaleTimestamp {}.encode())
+        );
+    }
+
+    #[test]
+    fn owner_only_calls_revert_after_renouncing() {
+        let old_owner = Address::repeat_byte(0xA1);
+        assert_eq!(check_owner(old_owner, Address::ZERO), Err(NotOwner {}.encode()));
+        // Not even a call that somehow comes from the zero address passes
+        assert_eq!(check_owner(Address::ZERO, Address::ZERO), Err(NotOwner {}.encode()));
+    }
+
+    #[test]
+    fn guardian_can_pause_but_nothing_more() {
+        let (guardian, stranger) = (Address::repeat_byte(0xD4), Address::repeat_byte(0xC3));
+        assert_eq!(check_pauser(guardian, false, guardian), Ok(()));
+        assert_eq!(check_pauser(stranger, false, guardian), Err(MissingRole {}.encode()));
+        assert_eq!(check_pauser(stranger, true, guardian), Ok(()));
+
+        // owner-only entry points such as ownership transfer still reject the guardian
+        assert_eq!(check_owner(guardian, Address::repeat_byte(0xA1)), Err(NotOwner {}.encode()));
+    }
+
+    #[test]
+    fn composite_is_no_fresher_than_its_inputs() {
+        let defi = TrustScore {
+            timestamp: NOW - 3_000,
+            validUntil: NOW + DAY,
+            ..source_score(80, 90)
+        };
+        let social = TrustScore {
+            timestamp: NOW - 60,
+            validUntil: NOW + 600,
+            ..source_score(40, 50)
+        };
+        let nft = TrustScore {
+            timestamp: NOW - 10,
+            ..source_score(60, 60)
+        };
+
+        let composite = weighted_blend(&[(5_000, defi), (2_500, social), (2_500, nft)], NOW).unwrap();
+        assert_eq!((composite.score, composite.confidence), (65, 72));
+        assert_eq!(composite.timestamp, NOW - 3_000);
+        // The earliest expiry wins; inputs that never expire do not reset it
+        assert_eq!(composite.validUntil, NOW + 600);
+    }
+
+    #[test]
+    fn composite_skips_expired_inputs() {
+        let expired = TrustScore {
+            validUntil: NOW - 1,
+            ..source_score(10, 10)
+        };
+        let live = TrustScore {
+            validUntil: NOW,
+            ..source_score(60, 70)
+        };
+        assert_eq!(blended(&[(5_000, expired.clone()), (5_000, live)]), Some((60, 70)));
+        assert_eq!(blended(&[(5_000, expired)]), None);
+    }
+
+    #[test]
+    fn derived_scores_take_no_source_slot() {
+        assert!(is_derived_source(COMPOSITE_SOURCE));
+        assert!(is_derived_source(BLEND_SOURCE));
+        assert!(!is_derived_source(FixedBytes::ZERO));
+    }
+
+    #[test]
+    fn category_tags_resolve_to_independent_categories() {
+        for category in ScoreCategory::ALL {
+            assert_eq!(ScoreCategory::from_tag(category.tag()).map(|c| c as u8), Some(category as u8));
+        }
+
+        // Every tag names its own category, so scores written under one never land in another
+        let tags: Vec<FixedBytes<32>> = ScoreCategory::ALL.into_iter().map(ScoreCategory::tag).collect();
+        for (i, tag) in tags.iter().enumerate() {
+            assert!(tags[i + 1..].iter().all(|other| other != tag));
+        }
+        assert_eq!(ScoreCategory::DeFi.tag(), keccak(b"TrustGrid.category.defi"));
+        assert_eq!(ScoreCategory::Gaming.tag(), keccak(b"TrustGrid.category.gaming"));
+    }
+
+    #[test]
+    fn missing_category_falls_back_to_the_default() {
+        assert_eq!(DEFAULT_CATEGORY, keccak(b"TrustGrid.category.aggregate"));
+        assert_eq!(
+            ScoreCategory::from_tag(FixedBytes::ZERO).map(|c| c as u8),
+            Some(ScoreCategory::Aggregate as u8)
+        );
+        assert!(ScoreCategory::from_tag(keccak(b"TrustGrid.category.unknown")).is_none());
+    }
+
+    fn score_update(score: u16, confidence: u16) -> ScoreUpdate {
+        ScoreUpdate {
+            wallet: Address::repeat_byte(0x01),
+            category: ScoreCategory::Aggregate as u8,
+            score,
+            confidence,
+            timestamp: NOW,
+            source: FixedBytes::ZERO,
+            metadata_hash: FixedBytes::ZERO,
+            deadline: NOW + 600,
+            valid_until: 0,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn confidence_is_part_of_the_signed_update() {
+        // A confident 60 and a guessed 60 are different signed messages
+        let confident = score_struct_hash(&score_update(60, 95), oracle(1), U256::ZERO);
+        let guessed = score_struct_hash(&score_update(60, 10), oracle(1), U256::ZERO);
+        assert_ne!(confident, guessed);
+    }
+
+    #[test]
+    fn blacklisted_wallet_is_untrusted_whatever_its_score() {
+        let top = source_score(100, 100);
+        assert!(clears_threshold(false, &top, 100, 70, NOW));
+        assert!(!clears_threshold(true, &top, 100, 70, NOW));
+    }
+
+    #[test]
+    fn signature_for_the_read_nonce_is_spent_by_the_update() {
+        // A fresh wallet's nonce is the zero storage default
+        let fresh = U256::default();
+        assert_eq!(fresh, U256::ZERO);
+
+        // The update signed over that nonce moves it on by one, and a second
+        // submission is then checked against a different message
+        let update = score_update(60, 90);
+        let signed = score_struct_hash(&update, oracle(1), fresh);
+        let next = fresh + U256::from(1);
+        assert_eq!(next, U256::from(1));
+        assert_ne!(signed, score_struct_hash(&update, oracle(1), next));
+    }
+
+    #[test]
+    fn status_age_and_staleness_agree() {
+        let max_age = DEFAULT_MAX_SCORE_AGE;
+        for timestamp in [NOW, NOW - max_age, NOW - max_age - 1, NOW - 2 * DAY] {
+            let age = score_age(timestamp, NOW);
+            assert_eq!(age, NOW - timestamp);
+            assert_eq!(is_absent_or_stale(timestamp, NOW, max_age), age > max_age);
+            assert_eq!(is_absent_or_stale(timestamp, NOW, max_age), is_stale(timestamp, NOW, max_age));
+        }
+
+        // An unscored wallet has no age but is never fresh
+        assert_eq!(score_age(0, NOW), 0);
+        assert!(is_absent_or_stale(0, NOW, max_age));
+    }
+
+    /// Slot of every `TrustOracle` field, laid out from the `sol_storage!` source with
+    /// the Solidity packing rules the storage macro follows
+    fn storage_layout() -> Vec<(&'static str, usize, usize)> {
+        let source = include_str!("main.rs");
+        let body = &source[source.find("pub struct TrustOracle {").unwrap()..];
+        let (mut layout, mut slot, mut offset) = (Vec::new(), 0, 0);
+
+        for line in body.lines().skip(1).take_while(|line| *line != "    }") {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line
+                .trim_end_matches(';')
+                .split_whitespace()
+                .filter(|token| *token != "public")
+                .collect();
+            let (name, ty) = (tokens[tokens.len() - 1], tokens[..tokens.len() - 1].join(" "));
+            let bytes_of = |ty: &str| match ty {
+                "address" => 20,
+                "bool" => 1,
+                "bytes32" => 32,
+                uint => uint.trim_start_matches("uint").parse::<usize>().unwrap() / 8,
+            };
+
+            // Mappings, arrays and strings start a fresh slot and leave the next field one too
+            let whole_slots = if ty.starts_with("mapping") || ty.ends_with("[]") || ty == "string" {
+                Some(1)
+            } else if let Some((element, len)) = ty.strip_suffix(']').and_then(|ty| ty.split_once('[')) {
+                let per_slot = 32 / bytes_of(element);
+                Some(len.parse::<usize>().unwrap().div_ceil(per_slot))
+            } else {
+                None
+            };
+
+            match whole_slots {
+                Some(slots) => {
+                    if offset != 0 {
+                        (slot, offset) = (slot + 1, 0);
+                    }
+                    layout.push((name, slot, slots));
+                    slot += slots;
+                }
+                None => {
+                    let size = bytes_of(&ty);
+                    if offset + size > 32 {
+                        (slot, offset) = (slot + 1, 0);
+                    }
+                    layout.push((name, slot, 1));
+                    offset += size;
+                }
+            }
+        }
+        layout
+    }
+
+    fn storage_slot(field: &str) -> (usize, usize) {
+        storage_layout()
+            .into_iter()
+            .find(|(name, ..)| *name == field)
+            .map(|(_, slot, slots)| (slot, slots))
+            .unwrap()
+    }
+
+    #[test]
+    fn storage_fields_keep_their_slots() {
+        // Fields of the first release; a reorder or removal above them moves these
+        for (field, slot) in [
+            ("trust_scores", 0),
+            ("oracle_address", 1),
+            ("owner", 2),
+            ("trust_threshold", 2),
+            ("nonces", 3),
+        ] {
+            assert_eq!(storage_slot(field).0, slot, "{field} moved");
+        }
+
+        // Fields added above the gap must shrink it by the slots they use, so it still ends here
+        let (gap, slots) = storage_slot("storage_gap");
+        assert_eq!(gap + slots, 138);
+        assert_eq!(storage_layout().last().unwrap().0, "storage_gap");
+    }
+
+    fn stored_at(timestamp: u32, score: u16) -> TrustScore {
+        TrustScore {
+            timestamp,
+            ..source_score(score, 90)
+        }
+    }
+
+    #[test]
+    fn out_of_order_replay_is_rejected() {
+        let stored = stored_at(NOW, 60);
+        assert_eq!(
+            check_against_stored(&stored, 55, NOW - 60, false, 0, 0, 0),
+            Err(NonMonotonicTimestamp {}.encode())
+        );
+        assert_eq!(check_against_stored(&stored, 55, NOW + 60, false, 0, 0, 0), Ok(()));
+    }
+
+    #[test]
+    fn equal_timestamp_update_is_rejected() {
+        let stored = stored_at(NOW, 60);
+        assert_eq!(
+            check_against_stored(&stored, 61, NOW, false, 0, 0, 0),
+            Err(NonMonotonicTimestamp {}.encode())
+        );
+        // A wallet's first write has nothing to be ordered against
+        assert_eq!(check_against_stored(&empty_score(), 61, NOW, false, 0, 0, 0), Ok(()));
+    }
+
+    #[test]
+    fn trusted_count_tracks_the_counted_status() {
+        let threshold = 70;
+        let (mut count, mut counted) = (U256::ZERO, false);
+        let mut write = |trusted: bool| {
+            count = trusted_count_after(count, counted, trusted);
+            counted = trusted;
+            count
+        };
+
+        // Written above the threshold: counted once
+        let score = source_score(80, 90);
+        assert_eq!(write(clears_threshold(false, &score, score.score, threshold, NOW)), U256::from(1));
+
+        // Decayed below the threshold without a write, then rewritten above it: the stored
+        // flag still says counted, so the wallet is not counted twice
+        let decayed = linear_decay(80, 5 * DAY, 1_000, 0);
+        assert!(!clears_threshold(false, &score, decayed, threshold, NOW));
+        assert_eq!(write(true), U256::from(1));
+
+        // Pushed below, then back above
+        assert_eq!(write(false), U256::ZERO);
+        assert_eq!(write(false), U256::ZERO);
+        assert_eq!(write(true), U256::from(1));
+
+        // Revoking a blacklisted wallet clears it by its flag even though it now reads untrusted
+        assert!(!clears_threshold(true, &score, score.score, threshold, NOW));
+        assert_eq!(trusted_count_after(count, counted, false), U256::ZERO);
+    }
+
+    #[test]
+    fn round_data_mirrors_the_stored_score() {
+        let stored = stored_at(NOW, 83);
+        let (round_id, answer, started_at, updated_at, answered_in_round) = round_data(&stored);
+        assert_eq!(answer, I256::try_from(83).unwrap());
+        assert_eq!(updated_at, U256::from(NOW));
+        assert_eq!(started_at, updated_at);
+        assert_eq!(round_id, U80::from(NOW));
+        assert_eq!(answered_in_round, round_id);
+
+        // An unscored wallet reads as round 0 with a zero answer
+        assert_eq!(round_data(&empty_score()), (U80::ZERO, I256::ZERO, U256::ZERO, U256::ZERO, U80::ZERO));
+    }
+
+    #[test]
+    fn mixed_revocation_batch_clears_only_valid_entries() {
+        let wallets = [Address::repeat_byte(0x01), Address::repeat_byte(0x02), Address::repeat_byte(0x03)];
+        let mut nonces = [U256::ZERO, U256::from(4), U256::ZERO];
+        let mut scored = [true, true, true];
+
+        // The middle wallet's revocation was signed over a stale nonce
+        let signed = [
+            revoke_struct_hash(wallets[0], oracle(1), U256::ZERO),
+            revoke_struct_hash(wallets[1], oracle(1), U256::from(3)),
+            revoke_struct_hash(wallets[2], oracle(1), U256::ZERO),
+        ];
+
+        let (revoked, failures) = apply_each(signed.into_iter().enumerate(), |(index, signed)| {
+            if signed != revoke_struct_hash(wallets[index], oracle(1), nonces[index]) {
+                return Err(InvalidSignature {}.encode());
+            }
+            scored[index] = false;
+            nonces[index] += U256::from(1);
+            Ok(())
+        });
+
+        assert_eq!(revoked, 2);
+        assert_eq!(failures, [(1, InvalidSignature {}.encode())]);
+        assert_eq!(scored, [false, true, false]);
+        assert_eq!(nonces, [U256::from(1), U256::from(4), U256::from(1)]);
+    }
+
+    #[test]
+    fn relayer_must_name_the_primary_oracle() {
+        let (primary, relayer) = (oracle(1), Address::repeat_byte(0xE5));
+        assert_eq!(check_submitter(relayer, primary, primary, true), Ok(()));
+        assert_eq!(check_submitter(relayer, oracle(2), primary, true), Err(UnauthorizedOracle {}.encode()));
+    }
+
+    #[test]
+    fn oracle_argument_is_ignored_when_the_oracle_submits() {
+        let primary = oracle(1);
+        assert_eq!(check_submitter(primary, Address::ZERO, primary, false), Ok(()));
+        assert_eq!(check_submitter(primary, oracle(2), primary, true), Ok(()));
+    }
+
+    #[test]
+    fn thousand_point_scale_bounds_scores() {
+        let max_score = 1_000;
+        assert_eq!(check_score_range(750, 90, max_score), Ok(()));
+        assert_eq!(check_score_range(1_000, 90, max_score), Ok(()));
+        assert_eq!(check_score_range(1_500, 90, max_score), Err(InvalidScore {}.encode()));
+
+        // The default scale still caps at 100
+        assert_eq!(check_score_range(750, 90, DEFAULT_MAX_SCORE), Err(InvalidScore {}.encode()));
+        assert_eq!(check_score_range(100, 101, DEFAULT_MAX_SCORE), Err(InvalidScore {}.encode()));
+    }
+
+    #[test]
+    fn linked_uri_is_returned_for_the_score_hash() {
+        let hash = keccak(b"explanation");
+        assert_eq!(
+            metadata_uri(String::from("ipfs://bafyexplanation"), String::from("https://x/"), hash),
+            "ipfs://bafyexplanation"
+        );
+    }
+
+    #[test]
+    fn unlinked_hash_falls_back_to_the_base_uri() {
+        let hash = FixedBytes::<32>::repeat_byte(0xab);
+        let uri = metadata_uri(String::new(), String::from("https://meta.example/"), hash);
+        assert_eq!(uri, String::from("https://meta.example/") + &"ab".repeat(32));
+    }
+
+    #[cfg(feature = "stylus_interface")]
+    #[test]
+    fn generated_interface_matches_the_snapshot() {
+        assert_eq!(
+            include_str!(concat!(env!("OUT_DIR"), "/ITrustOracle.sol")),
+            include_str!("../../abi/ITrustOracle.sol"),
+            "external ABI changed; copy $OUT_DIR/ITrustOracle.sol over contracts/abi/ITrustOracle.sol"
+        );
+    }
+
+    #[test]
+    fn score_swing_within_the_max_delta_is_accepted() {
+        let stored = stored_at(NOW - DAY, 90);
+        assert_eq!(check_against_stored(&stored, 60, NOW, false, 0, 30, 0), Ok(()));
+        assert_eq!(check_against_stored(&stored, 100, NOW, false, 0, 30, 0), Ok(()));
+        // Zero keeps the breaker off
+        assert_eq!(check_against_stored(&stored, 5, NOW, false, 0, 0, 0), Ok(()));
+    }
+
+    #[test]
+    fn score_swing_past_the_max_delta_is_rejected() {
+        let stored = stored_at(NOW - DAY, 90);
+        assert_eq!(
+            check_against_stored(&stored, 5, NOW, false, 0, 30, 0),
+            Err(ScoreDeltaTooLarge {}.encode())
+        );
+        assert_eq!(
+            check_against_stored(&stored_at(NOW - DAY, 5), 90, NOW, false, 0, 30, 0),
+            Err(ScoreDeltaTooLarge {}.encode())
+        );
+    }
+
+    #[test]
+    fn forced_update_overrides_the_max_delta() {
+        let stored = stored_at(NOW - DAY, 90);
+        assert_eq!(check_against_stored(&stored, 5, NOW, true, 0, 30, 0), Ok(()));
+
+        // The force flag is signed, so an unforced signature cannot be replayed as forced
+        let forced = ScoreUpdate { force: true, ..score_update(5, 90) };
+        assert_ne!(
+            score_struct_hash(&forced, oracle(1), U256::ZERO),
+            score_struct_hash(&score_update(5, 90), oracle(1), U256::ZERO)
+        );
+    }
+
+    #[test]
+    fn relayer_allowlist_gates_submitters_only_when_enabled() {
+        // Off by default: any caller may submit a signed update
+        assert_eq!(check_allowlisted_relayer(false, false), Ok(()));
+        assert_eq!(check_allowlisted_relayer(false, true), Ok(()));
+
+        assert_eq!(check_allowlisted_relayer(true, true), Ok(()));
+        assert_eq!(check_allowlisted_relayer(true, false), Err(UnauthorizedRelayer {}.encode()));
+    }
+
+    #[test]
+    fn batch_signature_covers_every_element_and_the_nonce() {
+        let wallets = [Address::repeat_byte(0x01), Address::repeat_byte(0x02)];
+        let shared = (NOW, FixedBytes::repeat_byte(0x0a), FixedBytes::repeat_byte(0x0b), NOW + 600);
+        let hash = |wallets: &[Address], scores: &[u16], confidences: &[u16], shared, nonce: u64| {
+            score_batch_struct_hash(wallets, scores, confidences, shared, oracle(1), U256::from(nonce))
+        };
+        let signed = hash(&wallets, &[70, 40], &[90, 80], shared, 0);
+
+        // The nonce is bumped after a successful batch, so the same signature cannot replay
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 80], shared, 1), signed);
+
+        // Tampering with any element yields a different digest
+        assert_ne!(hash(&[wallets[0], Address::repeat_byte(0x03)], &[70, 40], &[90, 80], shared, 0), signed);
+        assert_ne!(hash(&[wallets[1], wallets[0]], &[70, 40], &[90, 80], shared, 0), signed);
+        assert_ne!(hash(&wallets, &[70, 41], &[90, 80], shared, 0), signed);
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 81], shared, 0), signed);
+        assert_ne!(hash(&wallets[..1], &[70], &[90], shared, 0), signed);
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 80], (NOW + 1, shared.1, shared.2, shared.3), 0), signed);
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 80], (NOW, FixedBytes::ZERO, shared.2, shared.3), 0), signed);
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 80], (NOW, shared.1, FixedBytes::ZERO, shared.3), 0), signed);
+        assert_ne!(hash(&wallets, &[70, 40], &[90, 80], (NOW, shared.1, shared.2, NOW), 0), signed);
+        assert_ne!(
+            score_batch_struct_hash(&wallets, &[70, 40], &[90, 80], shared, oracle(2), U256::ZERO),
+            signed
+        );
+    }
+
+    #[test]
+    fn each_update_mints_a_fresh_token_id() {
+        let wallet = Address::repeat_byte(0x01);
+        let first = score_nft_token_id(wallet, oracle(1), U256::ZERO);
+        // The next update of the same wallet consumes the next nonce
+        assert_ne!(score_nft_token_id(wallet, oracle(1), U256::from(1)), first);
+        // A retired key's nonce space does not collide with its successor's
+        assert_ne!(score_nft_token_id(wallet, oracle(2), U256::ZERO), first);
+        assert_ne!(score_nft_token_id(Address::repeat_byte(0x02), oracle(1), U256::ZERO), first);
+    }
+
+    #[test]
+    fn revert_data_decodes_to_the_failing_check() {
+        // What a dApp sees: the first four bytes of the revert data pick the error
+        let selector = |revert: Result<(), Vec<u8>>| -> [u8; 4] { revert.unwrap_err()[..4].try_into().unwrap() };
+
+        assert_eq!(selector(check_score_range(101, 50, 100)), InvalidScore::SELECTOR);
+        assert_eq!(selector(check_score_range(50, 101, 100)), InvalidScore::SELECTOR);
+        assert_eq!(selector(check_owner(oracle(2), oracle(1))), NotOwner::SELECTOR);
+        assert_eq!(selector(check_not_paused(true)), ContractPaused::SELECTOR);
+        assert_eq!(
+            selector(check_update_times(NOW - 2 * DEFAULT_MAX_SCORE_AGE, NOW + 600, 0, NOW, DEFAULT_MAX_SCORE_AGE)),
+            StaleTimestamp::SELECTOR
+        );
+        assert_eq!(
+            selector(check_update_times(NOW, NOW - 1, 0, NOW, DEFAULT_MAX_SCORE_AGE)),
+            SignatureExpired::SELECTOR
+        );
+        assert_eq!(
+            selector(check_quorum(&[(oracle(1), Some(1))], 2, 0, U256::from(2))),
+            QuorumNotMet::SELECTOR
+        );
+
+        // Each payload also decodes as its error and as no other
+        let revert = check_score_range(101, 50, 100).unwrap_err();
+        assert!(InvalidScore::decode(&revert, true).is_ok());
+        assert!(StaleTimestamp::decode(&revert, true).is_err());
+    }
+
+    #[test]
+    fn retired_key_verifies_only_during_the_grace_window() {
+        let rotated_at = NOW as u64;
+        let expiry = rotated_at + ORACLE_ROTATION_GRACE_SECONDS;
+        // A score queued before the rotation lands while the window is open
+        assert!(in_rotation_grace(oracle(1), expiry, NOW - 60, rotated_at + 60));
+        assert!(in_rotation_grace(oracle(1), expiry, NOW - 60, expiry));
+        // and fails once it has closed
+        assert!(!in_rotation_grace(oracle(1), expiry, NOW - 60, expiry + 1));
+        // No rotation happened
+        assert!(!in_rotation_grace(Address::ZERO, expiry, NOW - 60, rotated_at + 60));
+    }
+
+    #[test]
+    fn retired_key_cannot_sign_scores_computed_after_the_rotation() {
+        let expiry = NOW as u64 + ORACLE_ROTATION_GRACE_SECONDS;
+        assert!(in_rotation_grace(oracle(1), expiry, NOW, NOW as u64 + 60));
+        assert!(!in_rotation_grace(oracle(1), expiry, NOW + 1, NOW as u64 + 60));
+    }
+
+    #[test]
+    fn oracle_weight_requires_the_staking_requirement() {
+        let required = U256::from(1_000);
+        assert_eq!(check_stake(U256::from(999), required), Err(InsufficientStake {}.encode()));
+        assert_eq!(check_stake(required, required), Ok(()));
+        // A zero requirement admits unstaked oracles
+        assert_eq!(check_stake(U256::ZERO, U256::ZERO), Ok(()));
+    }
+
+    #[test]
+    fn unscored_wallet_reads_the_default_with_no_timestamp() {
+        let defaulted = score_or_default(empty_score(), true, 50);
+        assert_eq!((defaulted.score, defaulted.timestamp), (50, 0));
+        assert!(is_absent_or_stale(defaulted.timestamp, NOW, DEFAULT_MAX_SCORE_AGE));
+    }
+
+    #[test]
+    fn real_score_overrides_the_default() {
+        let scored = score_or_default(stored_at(NOW, 20), false, 50);
+        assert_eq!((scored.score, scored.timestamp), (20, NOW));
+
+        // A legitimately stored 0 is not replaced by the default either
+        assert_eq!(score_or_default(stored_at(NOW, 0), false, 50).score, 0);
+    }
+
+    /// Fields of `struct TrustScore` in a Solidity source, comments stripped
+    fn trust_score_fields(source: &str) -> Vec<&str> {
+        let body = source.split("struct TrustScore {").nth(1).unwrap();
+        let body = body.split('}').next().unwrap();
+        body.lines()
+            .map(|line| line.split("//").next().unwrap().trim())
+            .filter(|field| !field.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn integrator_interface_matches_the_stylus_abi() {
+        let generated = include_str!("../../abi/ITrustOracle.sol");
+        let handwritten = include_str!("../../solidity/interfaces/ITrustOracle.sol");
+        let interface = handwritten.split("library TrustCheck").next().unwrap();
+
+        let functions: Vec<&str> = interface
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("function "))
+            .collect();
+        assert!(!functions.is_empty());
+        for function in functions {
+            assert!(generated.contains(function), "`{function}` is not in the Stylus ABI");
+        }
+        assert_eq!(trust_score_fields(interface), trust_score_fields(generated));
+    }
+
+    #[test]
+    fn appeal_correction_is_checked_like_an_update() {
+        let wallet = Address::repeat_byte(0x01);
+        let update = appeal_update(wallet, 85, 90, FixedBytes::ZERO, NOW);
+        assert_eq!(
+            check_update_times(update.timestamp, update.deadline, update.valid_until, NOW, DEFAULT_MAX_SCORE_AGE),
+            Ok(())
+        );
+        assert_eq!(
+            check_score_range(appeal_update(wallet, 101, 90, FixedBytes::ZERO, NOW).score, 90, 100),
+            Err(InvalidScore {}.encode())
+        );
+
+        let check = |stored: &TrustScore, update: &ScoreUpdate| {
+            check_against_stored(stored, update.score, update.timestamp, update.force, 0, 30, 0)
+        };
+        // Reversing a bad model run may swing past the circuit breaker
+        assert_eq!(check(&stored_at(NOW - DAY, 5), &update), Ok(()));
+        // but cannot overwrite a score written in the same block
+        assert_eq!(check(&stored_at(NOW, 5), &update), Err(NonMonotonicTimestamp {}.encode()));
+    }
+
+    #[test]
+    fn mirrored_copy_must_fit_the_scale_and_arrive_in_order() {
+        let stored = stored_at(NOW, 60);
+        assert_eq!(check_mirrored_score(&stored, 5, 90, NOW + 1, 100), Ok(()));
+        assert_eq!(check_mirrored_score(&empty_score(), 60, 90, NOW, 100), Ok(()));
+
+        assert_eq!(check_mirrored_score(&stored, 101, 90, NOW + 1, 100), Err(InvalidScore {}.encode()));
+        assert_eq!(check_mirrored_score(&stored, 60, 101, NOW + 1, 100), Err(InvalidScore {}.encode()));
+        // A delayed copy of an older score cannot roll the standby back
+        assert_eq!(
+            check_mirrored_score(&stored, 40, 90, NOW - 60, 100),
+            Err(NonMonotonicTimestamp {}.encode())
+        );
+        assert_eq!(check_mirrored_score(&stored, 40, 90, NOW, 100), Err(NonMonotonicTimestamp {}.encode()));
+    }
+
+    #[test]
+    fn removing_the_last_oracle_moves_nothing() {
+        assert_eq!(swap_remove_source(4, 5), None);
+        assert_eq!(swap_remove_source(0, 1), None);
+        assert_eq!(swap_remove_source(1, 5), Some(4));
+    }
+
+    #[test]
+    fn twa_weights_each_score_by_its_time_in_the_window() {
+        let history = [stored_at(NOW - 1000, 80), stored_at(NOW - 600, 40), stored_at(NOW - 100, 60)];
+        // 80 for 200s (from the window start), 40 for 500s, 60 for 100s: 42000 / 800 = 52.5
+        assert_eq!(time_weighted_average(&history, NOW - 800, NOW), Some(52));
+        // Only the latest score was in force over the last 100 seconds
+        assert_eq!(time_weighted_average(&history, NOW - 100, NOW), Some(60));
+    }
+
+    #[test]
+    fn twa_falls_back_when_no_entry_covers_the_window() {
+        assert_eq!(time_weighted_average(&[], NOW - 800, NOW), None);
+        // A score written this block has been in force for no time yet
+        assert_eq!(time_weighted_average(&[stored_at(NOW, 70)], NOW - 800, NOW), None);
+        // An old score covers the whole window on its own
+        assert_eq!(time_weighted_average(&[stored_at(NOW - 5 * DAY, 90)], NOW - 800, NOW), Some(90));
+    }
+
+    #[test]
+    fn score_delta_follows_a_precision_change() {
+        let (previous, current) = (stored_at(NOW - DAY, 40), stored_at(NOW, 55));
+        assert_eq!(score_delta(&previous, &current), (15, NOW - DAY));
+        assert_eq!(
+            score_delta(&rescaled(previous, 100, 10_000), &rescaled(current, 100, 10_000)),
+            (1500, NOW - DAY)
+        );
+        assert_eq!(score_delta(&empty_score(), &current), (0, 0));
+    }
+
+    #[test]
+    fn rescaled_history_keeps_its_averages() {
+        let history = [stored_at(NOW - 1000, 80), stored_at(NOW - 600, 40), stored_at(NOW - 100, 60)];
+        let basis_points: Vec<TrustScore> = history.into_iter().map(|entry| rescaled(entry, 100, 10_000)).collect();
+        assert_eq!(time_weighted_average(&basis_points, NOW - 800, NOW), Some(5250));
+    }
+
+    #[test]
+    fn reblending_unchanged_sources_is_not_newer() {
+        let expiring = TrustScore {
+            validUntil: NOW + DAY,
+            ..stored_at(NOW - 100, 50)
+        };
+        let sources = [(6_000, stored_at(NOW - 500, 70)), (4_000, expiring)];
+        let blend = TrustScore {
+            source: BLEND_SOURCE,
+            ..weighted_blend(&sources, NOW).unwrap()
+        };
+        assert_eq!((blend.score, blend.timestamp, blend.validUntil), (62, NOW - 500, NOW + DAY));
+
+        // The same inputs an hour later blend to the same timestamp, which `blend_scores`
+        // rejects as not newer than the stored aggregate
+        let again = weighted_blend(&sources, NOW + 3_600).unwrap();
+        assert_eq!(again.timestamp, blend.timestamp);
+    }
+
+    #[test]
+    fn attestation_past_valid_until_is_untrusted() {
+        let attested = TrustScore {
+            validUntil: NOW,
+            ..stored_at(NOW - DAY, 95)
+        };
+        assert!(clears_threshold(false, &attested, attested.score, 70, NOW));
+        assert!(!clears_threshold(false, &attested, attested.score, 70, NOW + 1));
+
+        // 0 means the oracle set no expiry
+        let open_ended = stored_at(NOW - DAY, 95);
+        assert!(clears_threshold(false, &open_ended, open_ended.score, 70, u32::MAX));
+    }
+
+    #[test]
+    fn withdraw_leaves_stakes_in_place() {
+        let staked = U256::from(3_000);
+        // Fees and slashed stake above the staked total are recoverable
+        assert_eq!(withdrawable(U256::from(4_250), staked), U256::from(1_250));
+        assert_eq!(withdrawable(staked, staked), U256::ZERO);
+        assert_eq!(withdrawable(U256::from(1_000), staked), U256::ZERO);
+    }
+
+    /// Storage-backed tests: native stand-ins for the Stylus `vm_hooks` host functions
+    /// let them instantiate `TrustOracle` and drive its entry points against real storage.
+    ///
+    /// Hostio caching would pin the first sender and timestamp a process reads, so these
+    /// run without it: `cargo test --no-default-features`.
+    #[cfg(not(feature = "hostio-caching"))]
+    mod storage {
+        use super::*;
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+        use std::rc::Rc;
+        use std::sync::{Mutex, MutexGuard, PoisonError};
+        use stylus_sdk::alloy_sol_types::SolEvent;
+        use stylus_sdk::storage::{StorageCache, StorageType};
+
+        /// Where `Vm::new` deploys the contract under test
+        const CONTRACT: Address = Address::new([0xc0; 20]);
+        /// Deployer, and so owner and admin
+        const OWNER: Address = Address::new([0xa1; 20]);
+        /// Primary oracle: an ERC-1271 wallet, so tests sign without ECDSA keys
+        const ORACLE: Address = Address::new([0x0a; 20]);
+        const RELAYER: Address = Address::new([0xe1; 20]);
+        const STRANGER: Address = Address::new([0x5e; 20]);
+        const WALLET: Address = Address::new([0x01; 20]);
+
+        type Handler = Rc<RefCell<dyn FnMut(&[u8]) -> Result<Vec<u8>, Vec<u8>>>>;
+
+        /// A log as `emit_log` received it
+        struct Log {
+            topics: Vec<FixedBytes<32>>,
+            data: Vec<u8>,
+        }
+
+        #[derive(Default)]
+        struct Host {
+            storage: HashMap<(Address, FixedBytes<32>), FixedBytes<32>>,
+            contract: Address,
+            deployed: Vec<Address>,
+            sender: Address,
+            value: U256,
+            timestamp: u64,
+            balances: HashMap<Address, U256>,
+            mocks: HashMap<Address, Handler>,
+            return_data: Vec<u8>,
+            logs: Vec<Log>,
+            /// (target, whether it was a static call) of every outgoing call
+            calls: Vec<(Address, bool)>,
+            loads: usize,
+            stores: usize,
+        }
+
+        thread_local! {
+            static HOST: RefCell<Host> = RefCell::new(Host::default());
+        }
+
+        /// The SDK's storage cache is process-wide, so harness tests run one at a time
+        static SERIAL: Mutex<()> = Mutex::new(());
+
+        fn with_host<R>(f: impl FnOnce(&mut Host) -> R) -> R {
+            HOST.with(|host| f(&mut host.borrow_mut()))
+        }
+
+        unsafe fn read_address(ptr: *const u8) -> Address {
+            Address::from_slice(std::slice::from_raw_parts(ptr, 20))
+        }
+
+        unsafe fn read_word(ptr: *const u8) -> FixedBytes<32> {
+            FixedBytes::from_slice(std::slice::from_raw_parts(ptr, 32))
+        }
+
+        unsafe fn write_bytes(dest: *mut u8, bytes: &[u8]) {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), dest, bytes.len());
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn storage_load_bytes32(key: *const u8, dest: *mut u8) {
+            let key = read_word(key);
+            let value = with_host(|host| {
+                host.loads += 1;
+                host.storage.get(&(host.contract, key)).copied().unwrap_or_default()
+            });
+            write_bytes(dest, value.as_slice());
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn storage_store_bytes32(key: *const u8, value: *const u8) {
+            let (key, value) = (read_word(key), read_word(value));
+            with_host(|host| {
+                host.stores += 1;
+                host.storage.insert((host.contract, key), value);
+            });
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn msg_sender(sender: *mut u8) {
+            write_bytes(sender, with_host(|host| host.sender).as_slice());
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn msg_value(value: *mut u8) {
+            write_bytes(value, &with_host(|host| host.value).to_be_bytes::<32>());
+        }
+
+        #[no_mangle]
+        extern "C" fn msg_reentrant() -> bool {
+            false
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn tx_origin(origin: *mut u8) {
+            msg_sender(origin);
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn contract_address(address: *mut u8) {
+            write_bytes(address, with_host(|host| host.contract).as_slice());
+        }
+
+        #[no_mangle]
+        extern "C" fn block_timestamp() -> u64 {
+            with_host(|host| host.timestamp)
+        }
+
+        #[no_mangle]
+        extern "C" fn block_number() -> u64 {
+            with_host(|host| host.timestamp / 12)
+        }
+
+        #[no_mangle]
+        extern "C" fn chainid() -> u64 {
+            42_161
+        }
+
+        #[no_mangle]
+        extern "C" fn evm_gas_left() -> u64 {
+            u64::MAX
+        }
+
+        #[no_mangle]
+        extern "C" fn evm_ink_left() -> u64 {
+            u64::MAX
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn account_balance(address: *const u8, dest: *mut u8) {
+            let address = read_address(address);
+            let balance = with_host(|host| host.balances.get(&address).copied().unwrap_or_default());
+            write_bytes(dest, &balance.to_be_bytes::<32>());
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn account_codehash(address: *const u8, dest: *mut u8) {
+            let address = read_address(address);
+            let has_code = with_host(|host| host.mocks.contains_key(&address) || host.deployed.contains(&address));
+            let hash = if has_code { keccak(address) } else { FixedBytes::ZERO };
+            write_bytes(dest, hash.as_slice());
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn emit_log(data: *const u8, len: usize, topics: usize) {
+            let bytes = std::slice::from_raw_parts(data, len);
+            let (topic_bytes, data) = bytes.split_at(topics * 32);
+            let topics = topic_bytes.chunks(32).map(FixedBytes::from_slice).collect();
+            with_host(|host| host.logs.push(Log { topics, data: data.to_vec() }));
+        }
+
+        #[no_mangle]
+        extern "C" fn return_data_size() -> usize {
+            with_host(|host| host.return_data.len())
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn read_return_data(dest: *mut u8, offset: usize, size: usize) -> usize {
+            let data = with_host(|host| host.return_data.clone());
+            let start = offset.min(data.len());
+            let copied = &data[start..(start + size).min(data.len())];
+            write_bytes(dest, copied);
+            copied.len()
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn static_call_contract(
+            contract: *const u8,
+            calldata: *const u8,
+            calldata_len: usize,
+            _gas: u64,
+            return_data_len: *mut usize,
+        ) -> u8 {
+            let calldata = std::slice::from_raw_parts(calldata, calldata_len);
+            dispatch(read_address(contract), calldata, U256::ZERO, true, return_data_len)
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn call_contract(
+            contract: *const u8,
+            calldata: *const u8,
+            calldata_len: usize,
+            value: *const u8,
+            _gas: u64,
+            return_data_len: *mut usize,
+        ) -> u8 {
+            let calldata = std::slice::from_raw_parts(calldata, calldata_len);
+            let value = U256::from_be_bytes(read_word(value).0);
+            dispatch(read_address(contract), calldata, value, false, return_data_len)
+        }
+
+        /// Run an outgoing call: mocks answer through their handler, with calls they make
+        /// back into the contract coming from the mock; any other address is an account
+        /// that accepts plain transfers
+        unsafe fn dispatch(target: Address, calldata: &[u8], value: U256, is_static: bool, return_data_len: *mut usize) -> u8 {
+            let (handler, caller, outer_sender) = with_host(|host| {
+                host.calls.push((target, is_static));
+                (host.mocks.get(&target).cloned(), host.contract, host.sender)
+            });
+
+            let result = match handler {
+                Some(handler) => {
+                    with_host(|host| host.sender = target);
+                    let result = (handler.borrow_mut())(calldata);
+                    with_host(|host| host.sender = outer_sender);
+                    result
+                }
+                None => Ok(Vec::new()),
+            };
+
+            let (status, data) = match result {
+                Ok(data) => (0, data),
+                Err(data) => (1, data),
+            };
+            *return_data_len = data.len();
+            with_host(|host| {
+                if status == 0 && value != U256::ZERO {
+                    let from = host.balances.entry(caller).or_default();
+                    *from = from.saturating_sub(value);
+                    *host.balances.entry(target).or_default() += value;
+                }
+                host.return_data = data;
+            });
+            status
+        }
+
+        /// The contract at the address calls are currently sent to, as its entrypoint sees it
+        fn instance() -> TrustOracle {
+            unsafe { TrustOracle::new(U256::ZERO, 0) }
+        }
+
+        /// A fresh chain with `TrustOracle` deployed at `CONTRACT` and initialized by `OWNER`
+        /// (threshold 70, scores out of 100, default score 50) with `ORACLE` as its oracle
+        struct Vm {
+            _serial: MutexGuard<'static, ()>,
+        }
+
+        impl Vm {
+            fn new() -> Self {
+                let serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+                StorageCache::clear();
+                with_host(|host| {
+                    *host = Host {
+                        timestamp: NOW as u64,
+                        ..Host::default()
+                    }
+                });
+
+                let vm = Self { _serial: serial };
+                vm.mock(ORACLE, erc1271_wallet);
+                vm.deploy(CONTRACT);
+                vm
+            }
+
+            /// Deploy and initialize another instance; later calls go to it
+            fn deploy(&self, at: Address) {
+                self.switch_to(at);
+                with_host(|host| host.deployed.push(at));
+                self.call(OWNER, |contract| contract.init(ORACLE, 70, 100, 50)).unwrap();
+            }
+
+            /// Send later calls to the instance deployed at `at`
+            fn switch_to(&self, at: Address) {
+                StorageCache::flush();
+                StorageCache::clear();
+                with_host(|host| host.contract = at);
+            }
+
+            /// Answer calls to `at` with `handler`, which gets the calldata and returns the
+            /// return data or, as `Err`, the revert data
+            fn mock(&self, at: Address, handler: impl FnMut(&[u8]) -> Result<Vec<u8>, Vec<u8>> + 'static) {
+                let handler: Handler = Rc::new(RefCell::new(handler));
+                with_host(|host| host.mocks.insert(at, handler));
+            }
+
+            /// Call an entry point as `sender` with `value` attached. Like a transaction, its
+            /// writes, logs and the value transfer only persist if it succeeds.
+            fn send<T>(
+                &self,
+                sender: Address,
+                value: U256,
+                entry_point: impl FnOnce(&mut TrustOracle) -> Result<T, Vec<u8>>,
+            ) -> Result<T, Vec<u8>> {
+                let logs = with_host(|host| {
+                    host.sender = sender;
+                    host.value = value;
+                    host.logs.len()
+                });
+
+                let result = entry_point(&mut instance());
+
+                if result.is_ok() {
+                    StorageCache::flush();
+                }
+                StorageCache::clear();
+                with_host(|host| {
+                    if result.is_ok() {
+                        *host.balances.entry(host.contract).or_default() += value;
+                    } else {
+                        host.logs.truncate(logs);
+                    }
+                    host.value = U256::ZERO;
+                });
+                result
+            }
+
+            fn call<T>(
+                &self,
+                sender: Address,
+                entry_point: impl FnOnce(&mut TrustOracle) -> Result<T, Vec<u8>>,
+            ) -> Result<T, Vec<u8>> {
+                self.send(sender, U256::ZERO, entry_point)
+            }
+
+            /// Read from the current instance without a transaction
+            fn view<T>(&self, query: impl FnOnce(&TrustOracle) -> T) -> T {
+                let result = query(&instance());
+                StorageCache::clear();
+                result
+            }
+
+            /// Move the block timestamp forward
+            fn warp(&self, seconds: u64) {
+                with_host(|host| host.timestamp += seconds);
+            }
+
+            /// Current block timestamp
+            fn now(&self) -> u32 {
+                with_host(|host| host.timestamp as u32)
+            }
+
+            /// Events of type `E` emitted so far, oldest first
+            fn events<E: SolEvent>(&self) -> Vec<E> {
+                with_host(|host| {
+                    host.logs
+                        .iter()
+                        .filter(|log| log.topics.first() == Some(&E::SIGNATURE_HASH))
+                        .map(|log| E::decode_log(log.topics.iter().copied(), &log.data, true).unwrap())
+                        .collect()
+                })
+            }
+
+            /// (target, static) of every outgoing call so far
+            fn calls(&self) -> Vec<(Address, bool)> {
+                with_host(|host| host.calls.clone())
+            }
+
+            /// Storage words (loaded, stored) by the transactions since the last call
+            fn take_storage_ops(&self) -> (usize, usize) {
+                with_host(|host| (std::mem::take(&mut host.loads), std::mem::take(&mut host.stores)))
+            }
+        }
+
+        /// `isValidSignature` of a wallet whose signature over a digest is the digest itself
+        fn erc1271_wallet(calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+            // selector (4) || hash (32) || offset (32) || length (32) || signature
+            let hash = &calldata[4..36];
+            let len = U256::from_be_slice(&calldata[68..100]).to::<usize>();
+            let signature = &calldata[100..100 + len];
+
+            let mut magic = vec![0; 32];
+            if signature == hash {
+                magic[..4].copy_from_slice(ERC1271_MAGIC_VALUE.as_slice());
+            }
+            Ok(magic)
+        }
+
+        /// `ORACLE`'s signature over `update` at its current nonce
+        fn sign(vm: &Vm, update: &ScoreUpdate) -> Vec<u8> {
+            vm.view(|contract| {
+                let nonce = contract.oracle_nonces.get(update.wallet).get(ORACLE);
+                contract.create_message_hash(update, ORACLE, nonce).to_vec()
+            })
+        }
+
+        /// Submit `update` through `update_score` as `sender`, naming `ORACLE` as the signer
+        fn submit(vm: &Vm, sender: Address, update: &ScoreUpdate) -> Result<(), Vec<u8>> {
+            submit_signed(vm, sender, update, sign(vm, update))
+        }
+
+        /// `submit` with a given signature
+        fn submit_signed(vm: &Vm, sender: Address, update: &ScoreUpdate, signature: Vec<u8>) -> Result<(), Vec<u8>> {
+            vm.call(sender, |contract| {
+                contract.update_score(
+                    update.wallet,
+                    update.category,
+                    update.score,
+                    update.confidence,
+                    update.timestamp,
+                    update.source,
+                    update.metadata_hash,
+                    update.deadline,
+                    update.valid_until,
+                    ORACLE,
+                    signature,
+                )
+            })
+        }
+
+        /// `score_update` for `WALLET` at the current block time
+        fn update_now(vm: &Vm, score: u16) -> ScoreUpdate {
+            let now = vm.now();
+            ScoreUpdate {
+                timestamp: now,
+                deadline: now + 600,
+                ..score_update(score, 90)
+            }
+        }
+
+        #[test]
+        fn oracle_update_is_stored_and_bumps_the_nonce() {
+            let vm = Vm::new();
+            assert_eq!(submit(&vm, ORACLE, &update_now(&vm, 80)), Ok(()));
+
+            let stored = vm.view(|contract| contract.get_trust_score(WALLET));
+            assert_eq!((stored.score, stored.confidence, stored.timestamp), (80, 90, NOW));
+            assert_eq!(vm.view(|contract| contract.oracle_nonces.get(WALLET).get(ORACLE)), U256::from(1));
+
+            // A later update signed at the spent nonce no longer verifies
+            vm.warp(60);
+            let later = update_now(&vm, 85);
+            let spent = vm.view(|contract| contract.create_message_hash(&later, ORACLE, U256::ZERO).to_vec());
+            let (now, deadline) = (later.timestamp, later.deadline);
+            let replayed = vm.call(ORACLE, |contract| {
+                contract.update_score(WALLET, 0, 85, 90, now, FixedBytes::ZERO, FixedBytes::ZERO, deadline, 0, ORACLE, spent)
+            });
+            assert_eq!(replayed, Err(InvalidSignature {}.encode()));
+        }
+
+        #[test]
+        fn permit_checks_run_against_storage_and_keep_the_nonce() {
+            let vm = Vm::new();
+            let deadline = NOW as u64 + 600;
+
+            let wrong_caller = vm.call(STRANGER, |contract| contract.score_permit(WALLET, RELAYER, deadline, vec![0; 65]));
+            assert_eq!(wrong_caller.map(|_| ()), Err(NotPermitRequester {}.encode()));
+
+            let expired = vm.call(RELAYER, |contract| contract.score_permit(WALLET, RELAYER, NOW as u64 - 1, vec![0; 65]));
+            assert_eq!(expired.map(|_| ()), Err(SignatureExpired {}.encode()));
+
+            let malformed = vm.call(RELAYER, |contract| contract.score_permit(WALLET, RELAYER, deadline, vec![0; 12]));
+            assert_eq!(malformed.map(|_| ()), Err(InvalidSignature {}.encode()));
+
+            // Permit nonces are their own counter, untouched by failed permits and oracle updates
+            submit(&vm, ORACLE, &update_now(&vm, 80)).unwrap();
+            assert_eq!(vm.view(|contract| contract.get_permit_nonce(WALLET)), U256::ZERO);
+            assert!(vm.events::<ScorePermitUsed>().is_empty());
+        }
+
+        #[test]
+        fn small_or_early_changes_are_rejected_over_a_stored_score() {
+            let vm = Vm::new();
+            vm.call(OWNER, |contract| contract.set_min_score_delta(5)).unwrap();
+            vm.call(OWNER, |contract| contract.set_min_update_interval(3_600)).unwrap();
+            assert_eq!(
+                vm.call(STRANGER, |contract| contract.set_min_score_delta(0)),
+                Err(NotOwner {}.encode())
+            );
+
+            // The first score has nothing to compare against
+            submit(&vm, ORACLE, &update_now(&vm, 60)).unwrap();
+
+            vm.warp(7_200);
+            assert_eq!(submit(&vm, ORACLE, &update_now(&vm, 63)), Err(ScoreChangeInsignificant {}.encode()));
+            assert_eq!(submit(&vm, ORACLE, &update_now(&vm, 65)), Ok(()));
+
+            vm.warp(600);
+            assert_eq!(submit(&vm, ORACLE, &update_now(&vm, 90)), Err(UpdateTooFrequent {}.encode()));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 65);
+        }
+
+        #[test]
+        fn relayer_submits_only_for_the_primary_oracle() {
+            let vm = Vm::new();
+            vm.call(OWNER, |contract| contract.add_relayer(RELAYER)).unwrap();
+
+            assert_eq!(submit(&vm, RELAYER, &update_now(&vm, 80)), Ok(()));
+
+            // Naming another oracle is rejected on the single and both batch paths
+            let update = update_now(&vm, 85);
+            let signature = sign(&vm, &update);
+            let single = vm.call(RELAYER, |contract| {
+                contract.update_score(WALLET, 0, 85, 90, NOW, FixedBytes::ZERO, FixedBytes::ZERO, NOW + 600, 0, STRANGER, signature.clone())
+            });
+            assert_eq!(single, Err(UnauthorizedOracle {}.encode()));
+
+            let entry = (WALLET, 0, 85, 90, NOW, FixedBytes::ZERO, FixedBytes::ZERO, NOW + 600, 0, signature.clone());
+            let batch = vm.call(RELAYER, |contract| contract.batch_update_scores(vec![entry], STRANGER));
+            assert_eq!(batch, Err(UnauthorizedOracle {}.encode()));
+
+            let multi = vm.call(RELAYER, |contract| {
+                contract.update_score_multi(WALLET, 0, 85, 90, NOW, FixedBytes::ZERO, FixedBytes::ZERO, NOW + 600, 0, STRANGER, vec![signature])
+            });
+            assert_eq!(multi, Err(UnauthorizedOracle {}.encode()));
+
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 80);
+
+            // A removed relayer is an ordinary submitter again
+            vm.call(OWNER, |contract| contract.remove_relayer(RELAYER)).unwrap();
+            assert!(!vm.view(|contract| contract.trusted_relayers.get(RELAYER)));
+        }
+
+        #[test]
+        fn has_score_tracks_never_scored_scored_and_revoked_wallets() {
+            let vm = Vm::new();
+
+            // Never scored: the default score is served, but no score is held
+            assert!(!vm.view(|contract| contract.has_score(WALLET)));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 50);
+
+            // A stored score of 0 is still a score
+            submit(&vm, ORACLE, &update_now(&vm, 0)).unwrap();
+            assert!(vm.view(|contract| contract.has_score(WALLET)));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 0);
+
+            // Revoked: no score is held and the default is not served either
+            vm.call(ORACLE, |contract| contract.revoke_score_with_reason(WALLET, keccak(b"fraud"))).unwrap();
+            assert!(!vm.view(|contract| contract.has_score(WALLET)));
+            assert!(vm.view(|contract| contract.is_revoked(WALLET)));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 0);
+        }
+
+        #[test]
+        fn reentry_from_a_malicious_signer_is_rejected() {
+            let vm = Vm::new();
+
+            // The oracle wallet answers `isValidSignature` by submitting an update of its own
+            let reentry = Rc::new(RefCell::new(None));
+            let seen = Rc::clone(&reentry);
+            vm.mock(ORACLE, move |calldata| {
+                let nested = instance().update_score(
+                    WALLET,
+                    0,
+                    10,
+                    90,
+                    NOW,
+                    FixedBytes::ZERO,
+                    FixedBytes::ZERO,
+                    NOW + 600,
+                    0,
+                    ORACLE,
+                    Vec::new(),
+                );
+                *seen.borrow_mut() = Some(nested);
+                erc1271_wallet(calldata)
+            });
+
+            assert_eq!(submit(&vm, RELAYER, &update_now(&vm, 80)), Ok(()));
+            assert_eq!(*reentry.borrow(), Some(Err(ReentrancyGuardTriggered {}.encode())));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 80);
+
+            // The lock is released for the next top-level call
+            assert!(!vm.view(|contract| contract.reentrancy_locked.get()));
+            vm.warp(60);
+            assert_eq!(submit(&vm, RELAYER, &update_now(&vm, 85)), Ok(()));
+        }
+
+        #[test]
+        fn mirror_callback_into_a_guarded_write_is_rejected() {
+            let vm = Vm::new();
+            let mirror = Address::new([0x3d; 20]);
+            vm.call(OWNER, |contract| contract.set_mirror_oracle(mirror)).unwrap();
+
+            // A hostile mirror answers `forward_to_mirror` by calling `blend_scores` back
+            let reentry = Rc::new(RefCell::new(None));
+            let seen = Rc::clone(&reentry);
+            vm.mock(mirror, move |_| {
+                *seen.borrow_mut() = Some(instance().blend_scores(WALLET).map(|_| ()));
+                Ok(Vec::new())
+            });
+
+            assert_eq!(submit(&vm, ORACLE, &update_now(&vm, 80)), Ok(()));
+            assert_eq!(*reentry.borrow(), Some(Err(ReentrancyGuardTriggered {}.encode())));
+            // The copy went out as an ordinary call, after the score was written
+            assert!(vm.calls().contains(&(mirror, false)));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 80);
+            assert!(!vm.view(|contract| contract.reentrancy_locked.get()));
+        }
+
+        #[test]
+        fn proposed_owner_takes_over_on_accept() {
+            let vm = Vm::new();
+            let next = Address::new([0xb2; 20]);
+
+            assert_eq!(vm.call(OWNER, |contract| contract.propose_ownership(Address::ZERO)), Err(InvalidAddress {}.encode()));
+            assert_eq!(vm.call(STRANGER, |contract| contract.propose_ownership(next)), Err(NotOwner {}.encode()));
+
+            vm.call(OWNER, |contract| contract.propose_ownership(next)).unwrap();
+            assert_eq!(vm.view(|contract| contract.get_pending_owner()), next);
+            vm.call(next, |contract| contract.accept_ownership()).unwrap();
+
+            assert_eq!(vm.view(|contract| (contract.owner.get(), contract.get_pending_owner())), (next, Address::ZERO));
+            assert!(vm.view(|contract| contract.has_role(ADMIN_ROLE, next)));
+            assert!(!vm.view(|contract| contract.has_role(ADMIN_ROLE, OWNER)));
+
+            let proposed = vm.events::<OwnershipTransferProposed>();
+            assert_eq!(proposed.len(), 1);
+            assert_eq!((proposed[0].oldOwner, proposed[0].proposedOwner), (OWNER, next));
+            let transferred = vm.events::<OwnershipTransferred>();
+            assert_eq!(transferred.len(), 1);
+            assert_eq!((transferred[0].oldOwner, transferred[0].newOwner), (OWNER, next));
+        }
+
+        #[test]
+        fn owner_keeps_control_until_the_transfer_is_accepted() {
+            let vm = Vm::new();
+            let (first, second) = (Address::new([0xb2; 20]), Address::new([0xc3; 20]));
+
+            vm.call(OWNER, |contract| contract.transfer_ownership(first)).unwrap();
+            assert_eq!(vm.call(OWNER, |contract| contract.set_min_score_delta(1)), Ok(()));
+            assert_eq!(vm.call(first, |contract| contract.set_min_score_delta(2)), Err(NotOwner {}.encode()));
+
+            // A second transfer replaces the pending owner
+            vm.call(OWNER, |contract| contract.transfer_ownership(second)).unwrap();
+            assert_eq!(vm.call(first, |contract| contract.accept_ownership()), Err(NotPendingOwner {}.encode()));
+            assert_eq!(vm.call(STRANGER, |contract| contract.accept_ownership()), Err(NotPendingOwner {}.encode()));
+
+            vm.call(second, |contract| contract.accept_ownership()).unwrap();
+            assert_eq!(vm.view(|contract| contract.owner.get()), second);
+            assert_eq!(vm.call(OWNER, |contract| contract.set_min_score_delta(3)), Err(NotOwner {}.encode()));
+            assert_eq!(vm.call(second, |contract| contract.set_min_score_delta(3)), Ok(()));
+        }
+
+        #[test]
+        fn updates_revert_only_while_paused() {
+            let vm = Vm::new();
+            submit(&vm, ORACLE, &update_now(&vm, 60)).unwrap();
+
+            assert_eq!(vm.call(STRANGER, |contract| contract.pause()), Err(MissingRole {}.encode()));
+            vm.call(OWNER, |contract| contract.pause()).unwrap();
+
+            vm.warp(60);
+            let update = update_now(&vm, 80);
+            assert_eq!(submit(&vm, ORACLE, &update), Err(ContractPaused {}.encode()));
+            let entry = (WALLET, 0, 80, 90, update.timestamp, FixedBytes::ZERO, FixedBytes::ZERO, update.deadline, 0, sign(&vm, &update));
+            assert_eq!(
+                vm.call(ORACLE, |contract| contract.batch_update_scores(vec![entry], ORACLE)),
+                Err(ContractPaused {}.encode())
+            );
+
+            // Reads keep working while paused
+            assert!(vm.view(|contract| contract.is_paused()));
+            assert_eq!(vm.view(|contract| contract.get_trust_score(WALLET)).score, 60);
+            assert!(!vm.view(|contract| contract.is_trusted(WALLET)));
+
+            vm.call(OWNER, |contract| contract.unpause()).unwrap();
+            assert_eq!(submit(&vm, ORACLE, &update), Ok(()));
+            assert!(vm.view(|contract| contract.is_trusted(WALLET)));
+
+            assert_eq!(vm.events::<Paused>().len(), 1);
+            assert_eq!(vm.events::<Unpaused>()[0].by, OWNER);
+        }
+
+        #[test]
+        fn revoking_clears_the_entry_and_bumps_the_nonce() {
+            let vm = Vm::new();
+            submit(&vm, ORACLE, &update_now(&vm, 80)).unwrap();
+            assert!(vm.view(|contract| contract.is_trusted(WALLET)));
+
+            // Revocations are signed over the wallet's current nonce, here 1
+            let signature = vm.view(|contract| {
+                contract.typed_data_digest(revoke_struct_hash(WALLET, ORACLE, U256::from(1))).to_vec()
+            });
+            vm.call(RELAYER, |contract| contract.revoke_score(WALLET, signature.clone())).unwrap();
+
+            let cleared = vm.view(|contract| contract.get_trust_score(WALLET));
+            assert_eq!((cleared.score, cleared.confidence, cleared.timestamp), (0, 0, 0));
+            assert_eq!((cleared.source, cleared.metadataHash), (FixedBytes::ZERO, FixedBytes::ZERO));
+            assert!(!vm.view(|contract| contract.is_trusted(WALLET)));
+            assert_eq!(vm.view(|contract| contract.oracle_nonces.get(WALLET).get(ORACLE)), U256::from(2));
+
+            let revoked = vm.events::<ScoreRevoked>();
+            assert_eq!((revoked.len(), revoked[0].wallet, revoked[0].oracle), (1, WALLET, ORACLE));
+
+            // The spent signature cannot revoke again
+            assert_eq!(
+                vm.call(RELAYER, |contract| contract.revoke_score(WALLET, signature)),
+                Err(InvalidSignature {}.encode())
+            );
+        }
+
+        #[test]
+        fn signature_for_one_instance_is_rejected_by_another() {
+            // Two deployments sharing one oracle key
+            let vm = Vm::new();
+            let other = Address::new([0xc1; 20]);
+            vm.deploy(other);
+            let other_domain = vm.view(|contract| contract.get_domain_separator());
+
+            vm.switch_to(CONTRACT);
+            assert_ne!(vm.view(|contract| contract.get_domain_separator()), other_domain);
+            let update = update_now(&vm, 80);
+            let signature = sign(&vm, &update);
+
+            vm.switch_to(other);
+            assert_eq!(submit_signed(&vm, ORACLE, &update, signature.clone()), Err(InvalidSignature {}.encode()));
+            assert!(!vm.view(|contract| contract.has_score(WALLET)));
+
+            vm.switch_to(CONTRACT);
+            assert_eq!(submit_signed(&vm, ORACLE, &update, signature), Ok(()));
+        }
+
+        #[test]
+        fn full_history_evicts_the_oldest_entry() {
+            let vm = Vm::new();
+            vm.call(OWNER, |contract| contract.set_history_depth(3)).unwrap();
+            for score in [60, 65, 70, 75, 80] {
+                vm.warp(60);
+                submit(&vm, ORACLE, &update_now(&vm, score)).unwrap();
+            }
+
+            let scores = |history: Vec<TrustScore>| history.iter().map(|entry| entry.score).collect::<Vec<_>>();
+            assert_eq!(scores(vm.view(|contract| contract.get_score_history(WALLET))), vec![70, 75, 80]);
+            assert_eq!(scores(vm.view(|contract| contract.get_recent_scores(WALLET))), vec![80, 75, 70]);
+        }
+
+        #[test]
+        fn oracle_list_matches_the_registry_after_adds_and_removes() {
+            let vm = Vm::new();
+            for n in 1..=5 {
+                vm.call(OWNER, |contract| contract.add_oracle(oracle(n))).unwrap();
+            }
+            vm.warp(ORACLE_TIMELOCK_SECONDS);
+            for n in 1..=5 {
+                vm.call(STRANGER, |contract| contract.activate_oracle_weight(oracle(n))).unwrap();
+            }
+
+            // Middle, last, first, then a fresh registration
+            for removed in [oracle(3), oracle(5), ORACLE] {
+                vm.call(OWNER, |contract| contract.remove_oracle(removed)).unwrap();
+            }
+            vm.call(OWNER, |contract| contract.register_oracle(oracle(6), 2)).unwrap();
+            vm.warp(ORACLE_TIMELOCK_SECONDS);
+            vm.call(STRANGER, |contract| contract.activate_oracle_weight(oracle(6))).unwrap();
+
+            let listed = vm.view(|contract| contract.get_oracles());
+            let mut sorted = listed.clone();
+            sorted.sort();
+            assert_eq!(sorted, [oracle(1), oracle(2), oracle(4), oracle(6)]);
+            vm.view(|contract| {
+                for (position, listed) in listed.iter().enumerate() {
+                    assert_eq!(contract.oracle_list_index.get(*listed), U256::from(position + 1));
+                    assert!(contract.is_oracle(*listed));
+                }
+                for removed in [oracle(3), oracle(5), ORACLE] {
+                    assert_eq!(contract.oracle_list_index.get(removed), U256::ZERO);
+                    assert!(!contract.is_oracle(removed));
+                }
+                assert_eq!(contract.get_total_weight(), U256::from(5));
+            });
+
+            for oracle in listed {
+                vm.call(OWNER, |contract| contract.remove_oracle(oracle)).unwrap();
+            }
+            assert!(vm.view(|contract| contract.get_oracles()).is_empty());
+            assert_eq!(vm.view(|contract| contract.get_total_weight()), U256::ZERO);
+        }
+
+        /// Submit `updates` through `update_scores_batch` as `ORACLE`
+        fn submit_batch(vm: &Vm, updates: &[ScoreUpdate], signatures: Vec<Vec<u8>>) -> Result<u32, Vec<u8>> {
+            vm.call(ORACLE, |contract| {
+                contract.update_scores_batch(
+                    updates.iter().map(|update| update.wallet).collect(),
+                    updates.iter().map(|update| update.category).collect(),
+                    updates.iter().map(|update| update.score).collect(),
+                    updates.iter().map(|update| update.confidence).collect(),
+                    updates.iter().map(|update| update.timestamp).collect(),
+                    updates.iter().map(|update| update.source).collect(),
+                    updates.iter().map(|update| update.metadata_hash).collect(),
+                    updates.iter().map(|update| update.deadline).collect(),
+                    updates.iter().map(|update| update.valid_until).collect(),
+                    ORACLE,
+                    signatures,
+                )
+            })
+        }
+
+        /// One update per wallet `0x21..`, each signed at its wallet's current nonce
+        fn updates_for(vm: &Vm, count: u8) -> (Vec<ScoreUpdate>, Vec<Vec<u8>>) {
+            let updates: Vec<ScoreUpdate> = (1..=count)
+                .map(|n| ScoreUpdate {
+                    wallet: Address::repeat_byte(0x20 + n),
+                    ..update_now(vm, 80)
+                })
+                .collect();
+            let signatures = updates.iter().map(|update| sign(vm, update)).collect();
+            (updates, signatures)
+        }
+
+        #[test]
+        fn batch_touches_less_storage_than_single_updates() {
+            let vm = Vm::new();
+            let (updates, signatures) = updates_for(&vm, 5);
+            vm.take_storage_ops();
+            for (update, signature) in updates.iter().zip(signatures) {
+                submit_signed(&vm, ORACLE, update, signature).unwrap();
+            }
+            let singles = vm.take_storage_ops();
+
+            // The same updates on a fresh instance, in one call
+            vm.deploy(Address::new([0xc1; 20]));
+            let (updates, signatures) = updates_for(&vm, 5);
+            vm.take_storage_ops();
+            assert_eq!(submit_batch(&vm, &updates, signatures), Ok(5));
+            let batch = vm.take_storage_ops();
+
+            assert!(batch.0 < singles.0, "batch loads {} >= single loads {}", batch.0, singles.0);
+            assert!(batch.1 < singles.1, "batch stores {} >= single stores {}", batch.1, singles.1);
+        }
+
+        #[test]
+        fn bad_batch_entry_is_skipped_and_reported() {
+            let vm = Vm::new();
+            let (mut updates, mut signatures) = updates_for(&vm, 3);
+            updates[1].score = 81;
+            signatures[2] = vec![0; 65];
+
+            assert_eq!(submit_batch(&vm, &updates, signatures), Ok(1));
+            let failed = vm.events::<BatchEntryFailed>();
+            assert_eq!(failed.len(), 2);
+            assert_eq!((failed[0].index, failed[0].wallet), (U256::from(1), updates[1].wallet));
+            assert_eq!(failed[1].index, U256::from(2));
+            assert_eq!(failed[0].reason.to_vec(), InvalidSignature {}.encode());
+
+            vm.view(|contract| {
+                assert!(contract.has_score(updates[0].wallet));
+                for skipped in &updates[1..] {
+                    assert!(!contract.has_score(skipped.wallet));
+                    assert_eq!(contract.oracle_nonces.get(skipped.wallet).get(ORACLE), U256::ZERO);
+                }
+            });
+        }
+
+        #[test]
+        fn threshold_update_logs_the_old_and_new_values() {
+            let vm = Vm::new();
+            assert_eq!(
+                vm.call(STRANGER, |contract| contract.update_trust_threshold(85)),
+                Err(MissingRole {}.encode())
+            );
+            assert_eq!(vm.call(OWNER, |contract| contract.update_trust_threshold(101)), Err(InvalidThreshold {}.encode()));
+            assert!(vm.events::<ThresholdUpdated>().is_empty());
+
+            vm.call(OWNER, |contract| contract.update_trust_threshold(85)).unwrap();
+            vm.call(OWNER, |contract| contract.update_trust_threshold(60)).unwrap();
+
+            let updated = vm.events::<ThresholdUpdated>();
+            let values: Vec<(u16, u16, Address)> =
+                updated.iter().map(|event| (event.oldThreshold, event.newThreshold, event.by)).collect();
+            assert_eq!(values, [(70, 85, OWNER), (85, 60, OWNER)]);
+            assert_eq!(vm.view(|contract| contract.get_trust_threshold()), 60);
+        }
+
+        #[test]
+        fn update_from_below_to_above_threshold_crosses_it() {
+            let vm = Vm::new();
+            submit(&vm, ORACLE, &update_now(&vm, 50)).unwrap();
+            assert!(vm.events::<ScoreThresholdCrossed>().is_empty());
+
+            vm.warp(60);
+            let update = update_now(&vm, 80);
+            submit(&vm, ORACLE, &update).unwrap();
+            vm.warp(60);
+            submit(&vm, ORACLE, &update_now(&vm, 60)).unwrap();
+
+            let updated = vm.events::<ScoreUpdated>();
+            assert_eq!(updated.iter().map(|event| event.score).collect::<Vec<_>>(), [50, 80, 60]);
+            assert_eq!(
+                (updated[1].wallet, updated[1].confidence, updated[1].timestamp, updated[1].deadline),
+                (WALLET, 90, update.timestamp, update.deadline)
+            );
+
+            // Each flip is logged once, the 50 -> 80 update going up and the 80 -> 60 one down
+            let crossed = vm.events::<ScoreThresholdCrossed>();
+            let flips: Vec<(Address, bool, u16, u32)> = crossed
+                .iter()
+                .map(|event| (event.wallet, event.is_now_trusted, event.new_score, event.timestamp))
+                .collect();
+            assert_eq!(flips, [(WALLET, true, 80, update.timestamp), (WALLET, false, 60, update.timestamp + 60)]);
+        }
+
+        #[test]
+        fn score_updates_filter_by_source_topic() {
+            let vm = Vm::new();
+            let kyc = keccak(b"kyc-partner");
+            let model = keccak(b"onchain-model");
+            for source in [kyc, model, kyc] {
+                vm.warp(60);
+                submit(&vm, ORACLE, &ScoreUpdate { source, ..update_now(&vm, 80) }).unwrap();
+            }
+
+            // eth_getLogs with topics [ScoreUpdated, WALLET, kyc]
+            let topics: Vec<Vec<FixedBytes<32>>> = with_host(|host| {
+                host.logs
+                    .iter()
+                    .filter(|log| log.topics.first() == Some(&ScoreUpdated::SIGNATURE_HASH))
+                    .map(|log| log.topics.clone())
+                    .collect()
+            });
+            assert_eq!(topics.len(), 3);
+            assert!(topics.iter().all(|topics| topics.len() == 3 && topics[1][12..] == WALLET[..]));
+            assert_eq!(topics.iter().filter(|topics| topics[2] == kyc).count(), 2);
+        }
+
+        /// Storage words an entry point may touch, as (loads, stores). Stylus has no gas
+        /// meter outside a node, so SLOAD and SSTORE counts stand in for gas: a new write
+        /// on a hot path shows up as one more store.
+        const INIT_BUDGET: (usize, usize) = (40, 30);
+        const UPDATE_SCORE_BUDGET: (usize, usize) = (100, 32);
+        /// Per entry of `batch_update_scores`, on top of one `update_score`
+        const BATCH_ENTRY_BUDGET: (usize, usize) = (60, 28);
+
+        fn assert_within(name: &str, used: (usize, usize), budget: (usize, usize)) {
+            assert!(used.0 <= budget.0, "{name} loaded {} words, budget {}", used.0, budget.0);
+            assert!(used.1 <= budget.1, "{name} stored {} words, budget {}", used.1, budget.1);
+        }
+
+        #[test]
+        fn entry_points_stay_within_their_storage_budgets() {
+            let vm = Vm::new();
+            assert_within("init", vm.take_storage_ops(), INIT_BUDGET);
+
+            let update = update_now(&vm, 80);
+            let signature = sign(&vm, &update);
+            vm.take_storage_ops();
+            submit_signed(&vm, ORACLE, &update, signature).unwrap();
+            assert_within("update_score", vm.take_storage_ops(), UPDATE_SCORE_BUDGET);
+
+            let (updates, signatures) = updates_for(&vm, 5);
+            let entries = updates
+                .iter()
+                .zip(signatures)
+                .map(|(update, signature)| {
+                    let (wallet, category, score, confidence) = (update.wallet, update.category, update.score, update.confidence);
+                    (wallet, category, score, confidence, update.timestamp, update.source, update.metadata_hash, update.deadline, update.valid_until, signature)
+                })
+                .collect();
+            vm.take_storage_ops();
+            vm.call(ORACLE, |contract| contract.batch_update_scores(entries, ORACLE)).unwrap();
+            let budget = (
+                UPDATE_SCORE_BUDGET.0 + 4 * BATCH_ENTRY_BUDGET.0,
+                UPDATE_SCORE_BUDGET.1 + 4 * BATCH_ENTRY_BUDGET.1,
+            );
+            assert_within("batch_update_scores", vm.take_storage_ops(), budget);
+        }
+
+        #[test]
+        fn erc1271_signer_is_consulted_with_a_static_call() {
+            let vm = Vm::new();
+            submit(&vm, ORACLE, &update_now(&vm, 80)).unwrap();
+            let oracle_calls: Vec<(Address, bool)> = vm.calls().into_iter().filter(|(target, _)| *target == ORACLE).collect();
+            assert_eq!(oracle_calls, [(ORACLE, true)]);
+        }
+
+        #[test]
+        fn pending_oracle_additions_are_tracked_separately() {
+            let vm = Vm::new();
+            let (first, second) = (oracle(0x21), oracle(0x22));
+            vm.call(OWNER, |contract| contract.add_oracle(first)).unwrap();
+            vm.warp(60);
+            vm.call(OWNER, |contract| contract.register_oracle(second, 3)).unwrap();
+
+            let activation = NOW as u64 + ORACLE_TIMELOCK_SECONDS;
+            assert_eq!(vm.view(|contract| contract.get_pending_oracle_weight(first)), (1, activation));
+            assert_eq!(vm.view(|contract| contract.get_pending_oracle_weight(second)), (3, activation + 60));
+
+            // Activating one proposal leaves the other pending
+            vm.warp(ORACLE_TIMELOCK_SECONDS - 60);
+            vm.call(STRANGER, |contract| contract.activate_oracle_weight(first)).unwrap();
+            assert_eq!(
+                vm.call(STRANGER, |contract| contract.activate_oracle_weight(second)),
+                Err(OracleTimelockActive {}.encode())
+            );
+            assert_eq!(vm.view(|contract| contract.get_pending_oracle_weight(second)), (3, activation + 60));
 
-        // Recover public key and verify against oracle address
-        match stylus_sdk::crypto::ecrecover(message_hash, v, FixedBytes::from(r), FixedBytes::from(s)) {
-            Ok(recovered_address) => Ok(recovered_address == self.oracle_address.get()),
-            Err(_) => Ok(false),
+            vm.call(OWNER, |contract| contract.cancel_oracle_weight_proposal(second)).unwrap();
+            assert_eq!(vm.view(|contract| contract.get_pending_oracle_weight(second)), (0, 0));
+            vm.view(|contract| {
+                assert_eq!(contract.get_oracle_weight(first), 1);
+                assert!(!contract.is_oracle(second));
+            });
         }
     }
 }
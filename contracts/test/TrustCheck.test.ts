@@ -0,0 +1,57 @@
+import { expect } from "chai"
+import { ethers } from "hardhat"
+import type { MockTrustConsumer, MockTrustOracle } from "../typechain-types"
+import type { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers"
+
+describe("TrustCheck", () => {
+  // MockTrustOracle implements ITrustOracle, which mirrors the Stylus oracle's ABI;
+  // the Solidity TrustOracle has a different TrustScore layout and no requireTrusted
+  let trustOracle: MockTrustOracle
+  let consumer: MockTrustConsumer
+  let trustedWallet: SignerWithAddress
+  let untrustedWallet: SignerWithAddress
+  let unscoredWallet: SignerWithAddress
+
+  const TRUST_THRESHOLD = 60
+
+  beforeEach(async () => {
+    ;[, trustedWallet, untrustedWallet, unscoredWallet] = await ethers.getSigners()
+
+    const MockTrustOracle = await ethers.getContractFactory("MockTrustOracle")
+    trustOracle = await MockTrustOracle.deploy(TRUST_THRESHOLD)
+    await trustOracle.waitForDeployment()
+
+    const MockTrustConsumer = await ethers.getContractFactory("MockTrustConsumer")
+    consumer = await MockTrustConsumer.deploy(await trustOracle.getAddress())
+    await consumer.waitForDeployment()
+
+    await trustOracle.setScore(trustedWallet.address, 80)
+    await trustOracle.setScore(untrustedWallet.address, 20)
+  })
+
+  it("Should let trusted wallets through", async () => {
+    await consumer.connect(trustedWallet).guardedAction()
+    expect(await consumer.guardedCalls()).to.equal(1)
+  })
+
+  it("Should revert with WalletNotTrusted for untrusted wallets", async () => {
+    await expect(consumer.connect(untrustedWallet).guardedAction())
+      .to.be.revertedWithCustomError(trustOracle, "WalletNotTrusted")
+      .withArgs(untrustedWallet.address)
+  })
+
+  it("Should revert with WalletNotTrusted for unscored wallets", async () => {
+    await expect(consumer.connect(unscoredWallet).guardedAction())
+      .to.be.revertedWithCustomError(trustOracle, "WalletNotTrusted")
+      .withArgs(unscoredWallet.address)
+  })
+
+  it("Should decode the full TrustScore through the interface", async () => {
+    const oracleInterface = await ethers.getContractAt("ITrustOracle", await trustOracle.getAddress())
+    const trustScore = await oracleInterface.getTrustScore(trustedWallet.address)
+    expect(trustScore.score).to.equal(80)
+    expect(trustScore.confidence).to.equal(100)
+    expect(trustScore.validUntil).to.equal(0)
+    await expect(oracleInterface.requireTrusted(trustedWallet.address)).not.to.be.reverted
+  })
+})
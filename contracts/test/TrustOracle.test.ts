@@ -200,4 +200,87 @@ describe("TrustOracle", () => {
       await expect(trustOracle.updateTrustThreshold(101)).to.be.revertedWithCustomError(trustOracle, "InvalidThreshold")
     })
   })
+
+  describe("Update Fees", () => {
+    const FEE = ethers.parseEther("0.01")
+
+    const signUpdate = async (score: number, timestamp: number) => {
+      const messageHash = await trustOracle.getMessageHash(
+        testWallet.address,
+        score,
+        timestamp,
+        TEST_SOURCE,
+        TEST_METADATA_HASH,
+      )
+      return oracle.signMessage(ethers.getBytes(messageHash))
+    }
+
+    beforeEach(async () => {
+      await trustOracle.setUpdateFee(FEE)
+    })
+
+    it("Should default to a zero fee", async () => {
+      const TrustOracle = await ethers.getContractFactory("TrustOracle")
+      const fresh = await TrustOracle.deploy(oracle.address, TRUST_THRESHOLD)
+      expect(await fresh.updateFee()).to.equal(0)
+    })
+
+    it("Should reject updates paying less than the fee", async () => {
+      const timestamp = (await ethers.provider.getBlock("latest"))!.timestamp
+      const signature = await signUpdate(TEST_SCORE, timestamp)
+
+      await expect(
+        trustOracle.updateScore(testWallet.address, TEST_SCORE, timestamp, TEST_SOURCE, TEST_METADATA_HASH, signature, {
+          value: FEE - 1n,
+        }),
+      ).to.be.revertedWithCustomError(trustOracle, "InsufficientFee")
+    })
+
+    it("Should accumulate fees across updates", async () => {
+      for (const score of [70, 80]) {
+        const timestamp = (await ethers.provider.getBlock("latest"))!.timestamp
+        const signature = await signUpdate(score, timestamp)
+
+        await expect(
+          trustOracle.updateScore(testWallet.address, score, timestamp, TEST_SOURCE, TEST_METADATA_HASH, signature, {
+            value: FEE,
+          }),
+        )
+          .to.emit(trustOracle, "FeeCollected")
+          .withArgs(owner.address, FEE)
+      }
+
+      expect(await trustOracle.collectedFees()).to.equal(FEE * 2n)
+      expect(await ethers.provider.getBalance(await trustOracle.getAddress())).to.equal(FEE * 2n)
+
+      await trustOracle.withdraw(owner.address)
+      expect(await trustOracle.collectedFees()).to.equal(0)
+    })
+  })
+
+  describe("ETH Recovery", () => {
+    const DEPOSIT = ethers.parseEther("1")
+
+    beforeEach(async () => {
+      await user.sendTransaction({ to: await trustOracle.getAddress(), value: DEPOSIT })
+    })
+
+    it("Should accept plain ETH transfers", async () => {
+      expect(await ethers.provider.getBalance(await trustOracle.getAddress())).to.equal(DEPOSIT)
+    })
+
+    it("Should let the owner withdraw the balance", async () => {
+      await expect(trustOracle.withdraw(testWallet.address))
+        .to.emit(trustOracle, "Withdrawal")
+        .withArgs(testWallet.address, DEPOSIT)
+        .and.to.changeEtherBalances([trustOracle, testWallet], [-DEPOSIT, DEPOSIT])
+    })
+
+    it("Should reject non-owner withdrawal", async () => {
+      await expect(trustOracle.connect(user).withdraw(user.address)).to.be.revertedWithCustomError(
+        trustOracle,
+        "OwnableUnauthorizedAccount",
+      )
+    })
+  })
 })